@@ -0,0 +1,60 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+
+const LEN: usize = 1_000_000;
+
+fn vec_append(c: &mut Criterion) {
+    let mut group = c.benchmark_group("vec_append");
+
+    group.bench_function("append", |b| {
+        b.iter_batched(
+            || (vec![1u64; LEN], vec![2u64; LEN]),
+            |(mut left, mut right)| {
+                merge2::vec::append(&mut left, &mut right);
+                black_box(left)
+            },
+            BatchSize::LargeInput,
+        )
+    });
+
+    group.bench_function("append_presized", |b| {
+        b.iter_batched(
+            || {
+                let mut left = Vec::with_capacity(2 * LEN);
+                left.resize(LEN, 1u64);
+                (left, vec![2u64; LEN])
+            },
+            |(mut left, mut right)| {
+                merge2::vec::append(&mut left, &mut right);
+                black_box(left)
+            },
+            BatchSize::LargeInput,
+        )
+    });
+
+    group.bench_function("append_copy", |b| {
+        b.iter_batched(
+            || (vec![1u64; LEN], vec![2u64; LEN]),
+            |(mut left, mut right)| {
+                merge2::vec::append_copy(&mut left, &mut right);
+                black_box(left)
+            },
+            BatchSize::LargeInput,
+        )
+    });
+
+    group.bench_function("prepend", |b| {
+        b.iter_batched(
+            || (vec![1u64; LEN], vec![2u64; LEN]),
+            |(mut left, mut right)| {
+                merge2::vec::prepend(&mut left, &mut right);
+                black_box(left)
+            },
+            BatchSize::LargeInput,
+        )
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, vec_append);
+criterion_main!(benches);
@@ -4,24 +4,67 @@ extern crate proc_macro;
 
 use manyhow::bail;
 use proc_macro2::TokenStream;
-use quote::{quote, quote_spanned};
+use quote::{quote, quote_spanned, ToTokens};
 use syn::{Generics, Token};
 
 struct Field {
     name: syn::Member,
+    ty: syn::Type,
     span: proc_macro2::Span,
     attrs: FieldAttrs,
+    serde_skip: bool,
+    cfgs: Vec<syn::Attribute>,
+    /// The `merge` attributes, for the span of the errors about their combination with the struct.
+    merge_attrs: Vec<syn::Attribute>,
 }
 
+/// The `merge` attributes of a field.
 #[derive(Default)]
 struct FieldAttrs {
     skip: bool,
-    strategy: Option<syn::Path>,
+    strategy: Option<syn::Expr>,
+    /// The attribute that set `strategy`: `strategy`, `inner_strategy` or `default`.
+    strategy_key: Option<syn::Ident>,
+    reverse: bool,
 }
 
-enum FieldAttr {
+/// The `merge` attributes of the struct.
+#[derive(Default)]
+struct ContainerAttrs {
+    strategy: Option<syn::Expr>,
+    post: Option<syn::Path>,
+    bound: Option<Vec<syn::WherePredicate>>,
+    option_right: bool,
+    skip_default_other: bool,
+    /// The `clone_ref` attribute name, for the span of the `Clone` errors.
+    clone_ref: Option<syn::Ident>,
+    transparent: bool,
+    doc: Vec<syn::LitStr>,
+    respect_serde_skip: bool,
+    allow_noop: bool,
+}
+
+/// A parsed `merge` attribute with its name, for the errors about misplaced attributes.
+struct NamedAttr {
+    name: syn::Ident,
+    attr: Attr,
+}
+
+enum Attr {
     Skip,
-    Strategy(syn::Path),
+    Strategy(syn::Expr),
+    Post(syn::Path),
+    Bound(Vec<syn::WherePredicate>),
+    OptionRight(bool),
+    SkipDefaultOther,
+    CloneRef,
+    Transparent,
+    Doc(syn::LitStr),
+    RespectSerdeSkip,
+    Reverse,
+    InnerStrategy(syn::Expr),
+    Default(syn::Expr),
+    AllowNoop,
 }
 
 #[proc_macro_derive(Merge, attributes(merge))]
@@ -31,7 +74,7 @@ pub fn merge_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 
 fn impl_merge(input: syn::DeriveInput, dummy: &mut TokenStream) -> manyhow::Result<TokenStream> {
     let name = &input.ident;
-    let default_strategy = FieldAttrs::from(input.attrs.iter());
+    let container = ContainerAttrs::from_attrs(input.attrs.iter())?;
 
     let (impl_generics, orig_ty_generics, where_clause) = input.generics.split_for_impl();
     *dummy = quote! {
@@ -42,13 +85,83 @@ fn impl_merge(input: syn::DeriveInput, dummy: &mut TokenStream) -> manyhow::Resu
         }
     };
 
+    if let Some(repr) = input.attrs.iter().find(|attr| is_packed(attr)) {
+        bail!(
+            repr,
+            "merge2::Merge can't be derived for packed structs, because their fields can't be borrowed"
+        )
+    }
+
     if let syn::Data::Struct(syn::DataStruct { ref fields, .. }) = input.data {
+        let mut fields = fields
+            .iter()
+            .enumerate()
+            .map(Field::try_from)
+            .collect::<syn::Result<Vec<_>>>()?;
+        if container.transparent && fields.len() != 1 {
+            bail!(
+                name,
+                "The `transparent` attribute requires a struct with exactly one field, found {}",
+                fields.len()
+            )
+        }
+        if container.respect_serde_skip {
+            for field in fields.iter_mut() {
+                if field.serde_skip && field.attrs.strategy.is_none() {
+                    field.attrs.skip = true;
+                }
+            }
+        }
+        if container.transparent {
+            let field = &fields[0];
+            let attrs = &field.attrs;
+            if attrs.skip || attrs.strategy.is_some() || attrs.reverse {
+                let merge_attrs = &field.merge_attrs;
+                let message = "The `transparent` attribute merges the field with its `Merge` \
+                               implementation, so the field can't have `skip`, `reverse` or \
+                               strategy attributes";
+                // A `#[serde(skip)]` field has no `merge` attributes to point at
+                if merge_attrs.is_empty() {
+                    bail!(field.span, "{}", message)
+                }
+                bail!(quote!(#(#merge_attrs)*), "{}", message)
+            }
+            if container.strategy.is_some() {
+                bail!(
+                    name,
+                    "The `transparent` and `strategy` struct attributes are mutually exclusive"
+                )
+            }
+        }
+        if container.strategy.is_none() {
+            for field in fields.iter() {
+                if !field.attrs.skip && field.attrs.strategy.is_none() {
+                    check_mergeable(&field.ty)?;
+                }
+            }
+        }
+        // A transparent struct merges its field with `Merge`, even if it's an `Option`
+        if container.option_right && !container.transparent {
+            for field in fields.iter_mut() {
+                if field.attrs.strategy.is_none() && is_option(&field.ty) {
+                    field.attrs.strategy = Some(syn::parse_quote_spanned!(field.span=>
+                        ::merge2::option::overwrite_some
+                    ));
+                }
+            }
+        }
         Ok(impl_merge_for_struct(
             name,
-            fields,
-            default_strategy,
+            &fields,
+            container,
             input.generics,
         ))
+    } else if let syn::Data::Enum(ref data) = input.data {
+        bail!(
+            data.enum_token,
+            "merge2::Merge can't be derived for enums yet, implement it manually instead, \
+             see the docs of the `Merge` trait for an example"
+        )
     } else {
         bail!("merge2::Merge can only be derived for structs")
     }
@@ -56,27 +169,185 @@ fn impl_merge(input: syn::DeriveInput, dummy: &mut TokenStream) -> manyhow::Resu
 
 fn impl_merge_for_struct(
     name: &syn::Ident,
-    fields: &syn::Fields,
-    default_strategy: FieldAttrs,
-    generics: Generics,
+    fields: &[Field],
+    container: ContainerAttrs,
+    mut generics: Generics,
 ) -> TokenStream {
-    let assignments = gen_assignments(fields, default_strategy);
+    use syn::spanned::Spanned;
+
+    add_bounds(&mut generics, fields, &container);
+    let assignments = gen_assignments(fields, &container);
+    // The bound of `skip_default_other` only applies to the `Merge` impl, not to `merge_ref`
+    let mut merge_generics = generics.clone();
+    let early_return = container.skip_default_other.then(|| {
+        merge_generics
+            .make_where_clause()
+            .predicates
+            .push(syn::parse_quote!(
+                Self: ::core::default::Default + ::core::cmp::PartialEq
+            ));
+        quote! {
+            if *other == <Self as ::core::default::Default>::default() {
+                return;
+            }
+        }
+    });
+    // There is no stable way to emit a warning from a derive, so it uses a deprecated constant
+    let noop_warning = (!container.allow_noop
+        && container.post.is_none()
+        && fields.iter().all(|f| f.attrs.skip))
+    .then(|| {
+        quote_spanned! {name.span()=>
+            const _: () = {
+                #[deprecated(
+                    note = "merge2::Merge does nothing, because the struct has no merged fields, \
+                            add `#[merge(allow_noop)]` to the struct if it's intended"
+                )]
+                const MERGE_IS_A_NOOP: () = ();
+                MERGE_IS_A_NOOP
+            };
+        }
+    });
+    let post = container
+        .post
+        .map(|post| quote_spanned!(post.span()=> #post(self, other);));
+    let doc = &container.doc;
     let (impl_generics, orig_ty_generics, where_clause) = generics.split_for_impl();
+    let merge_where_clause = &merge_generics.where_clause;
+    let merge_ref = container.clone_ref.as_ref().map(|clone_ref| {
+        // A `Self: Clone` bound on a struct without type parameters would be a hard error at the
+        // derive, so it's only added to generic structs, the others fail at `clone_ref` instead.
+        // `Self: Merge` carries the bounds of the `Merge` impl, like the one of `skip_default_other`
+        let clone_bound = (generics.type_params().count() > 0).then(|| {
+            quote! {
+                where
+                    Self: ::core::clone::Clone + ::merge2::Merge,
+            }
+        });
+        let clone = quote_spanned! {clone_ref.span()=>
+            ::merge2::__private::clone_ref::<Self>(other)
+        };
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics #name #orig_ty_generics #where_clause {
+                /// Merges a clone of `other` into `self`.
+                pub fn merge_ref(&mut self, other: &Self)
+                #clone_bound
+                {
+                    ::merge2::Merge::merge(self, &mut #clone);
+                }
+            }
+        }
+    });
 
     quote! {
-        impl #impl_generics ::merge2::Merge for #name #orig_ty_generics #where_clause {
+        #( #[doc = #doc] )*
+        #[automatically_derived]
+        #[allow(clippy::all, clippy::pedantic)]
+        impl #impl_generics ::merge2::Merge for #name #orig_ty_generics #merge_where_clause {
             fn merge(&mut self, other: &mut Self) {
+                #early_return
                 #assignments
+                #post
             }
         }
+
+        #merge_ref
+        #noop_warning
+    }
+}
+
+/// Adds `FieldType: Merge` bounds for the fields merged by the `Merge` trait that use generic
+/// type parameters. Skipped fields and fields with a strategy don't add bounds.
+/// The `bound` attribute replaces these bounds.
+fn add_bounds(generics: &mut Generics, fields: &[Field], container: &ContainerAttrs) {
+    if let Some(bound) = &container.bound {
+        generics
+            .make_where_clause()
+            .predicates
+            .extend(bound.iter().cloned());
+        return;
+    }
+
+    let params: Vec<syn::Ident> = generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect();
+    if params.is_empty() || container.strategy.is_some() {
+        return;
+    }
+
+    let bounds: Vec<syn::WherePredicate> = fields
+        .iter()
+        .filter(|f| !f.attrs.skip && f.attrs.strategy.is_none())
+        .filter(|f| contains_ident(f.ty.to_token_stream(), &params))
+        .map(|f| {
+            let ty = &f.ty;
+            syn::parse_quote!(#ty: ::merge2::Merge)
+        })
+        .collect();
+    generics.make_where_clause().predicates.extend(bounds);
+}
+
+/// Rejects the types that can't implement `Merge` with a hint, instead of a trait bound error
+/// inside of the generated code.
+fn check_mergeable(ty: &syn::Type) -> syn::Result<()> {
+    let kind = match ty {
+        syn::Type::Ptr(_) => "raw pointers",
+        syn::Type::BareFn(_) => "function pointers",
+        _ => return Ok(()),
+    };
+    bail!(
+        ty,
+        "merge2::Merge isn't implemented for {}, add `#[merge(skip)]` or a `#[merge(strategy = ...)]` to the field",
+        kind
+    )
+}
+
+/// Checks if the type is syntactically an `Option`, ignoring aliases.
+fn is_option(ty: &syn::Type) -> bool {
+    if let syn::Type::Path(syn::TypePath { qself: None, path }) = ty {
+        if let Some(segment) = path.segments.last() {
+            return segment.ident == "Option"
+                && matches!(segment.arguments, syn::PathArguments::AngleBracketed(_));
+        }
+    }
+    false
+}
+
+/// Checks if the attribute is `#[serde(skip)]`, possibly with other serde options.
+fn is_serde_skip(attr: &syn::Attribute) -> bool {
+    use proc_macro2::TokenTree;
+
+    match &attr.meta {
+        syn::Meta::List(list) if list.path.is_ident("serde") => list
+            .tokens
+            .clone()
+            .into_iter()
+            .any(|token| matches!(token, TokenTree::Ident(ident) if ident == "skip")),
+        _ => false,
     }
 }
 
-fn gen_assignments(fields: &syn::Fields, default_strategy: FieldAttrs) -> TokenStream {
-    let fields = fields.iter().enumerate().map(Field::from);
-    let assignments = fields.filter_map(|f| {
+fn is_packed(attr: &syn::Attribute) -> bool {
+    let packed = syn::Ident::new("packed", proc_macro2::Span::call_site());
+    attr.path().is_ident("repr") && contains_ident(attr.meta.to_token_stream(), &[packed])
+}
+
+fn contains_ident(tokens: TokenStream, idents: &[syn::Ident]) -> bool {
+    use proc_macro2::TokenTree;
+
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => idents.contains(&ident),
+        TokenTree::Group(group) => contains_ident(group.stream(), idents),
+        _ => false,
+    })
+}
+
+fn gen_assignments(fields: &[Field], container: &ContainerAttrs) -> TokenStream {
+    let assignments = fields.iter().filter_map(|f| {
         if !f.attrs.skip {
-            Some(gen_assignment(&f, &default_strategy))
+            Some(gen_assignment(f, container))
         } else {
             None
         }
@@ -86,76 +357,263 @@ fn gen_assignments(fields: &syn::Fields, default_strategy: FieldAttrs) -> TokenS
     }
 }
 
-fn gen_assignment(field: &Field, default_strategy: &FieldAttrs) -> TokenStream {
+fn gen_assignment(field: &Field, container: &ContainerAttrs) -> TokenStream {
     use syn::spanned::Spanned;
 
     let name = &field.name;
-    if let Some(strategy) = &field.attrs.strategy {
-        quote_spanned!(strategy.span()=> #strategy(&mut self.#name, &mut other.#name);)
-    } else if let Some(default) = &default_strategy.strategy {
-        quote_spanned!(default.span()=> #default(&mut self.#name, &mut other.#name);)
+    let ty = &field.ty;
+    // The `cfg` attributes of the field remove the assignment together with the field
+    let cfgs = &field.cfgs;
+    // A reversed merge merges `self` into `other`, then moves the result back to `self`
+    let (left, right) = if field.attrs.reverse {
+        (quote!(other), quote!(self))
+    } else {
+        (quote!(self), quote!(other))
+    };
+    let swap_back = field
+        .attrs
+        .reverse
+        .then(|| quote!(::core::mem::swap(&mut self.#name, &mut other.#name);));
+    let assignment = if let Some(strategy) = field
+        .attrs
+        .strategy
+        .as_ref()
+        .or(container.strategy.as_ref())
+    {
+        // The typed binding makes a mismatched strategy fail at the attribute, naming the field type
+        quote_spanned! {strategy.span()=> {
+            let strategy = ::merge2::__private::strategy::<#ty, _>(#strategy);
+            strategy(&mut #left.#name, &mut #right.#name);
+        }}
     } else {
-        quote_spanned!(field.span=> ::merge2::Merge::merge(&mut self.#name, &mut other.#name);)
+        quote_spanned!(field.span=> ::merge2::Merge::merge(&mut #left.#name, &mut #right.#name);)
+    };
+    quote! {
+        #( #cfgs )*
+        {
+            #assignment
+            #swap_back
+        }
     }
 }
 
-impl From<(usize, &syn::Field)> for Field {
-    fn from(data: (usize, &syn::Field)) -> Self {
+impl TryFrom<(usize, &syn::Field)> for Field {
+    type Error = syn::Error;
+
+    fn try_from(data: (usize, &syn::Field)) -> syn::Result<Self> {
         use syn::spanned::Spanned;
 
         let (index, field) = data;
-        Field {
+        Ok(Field {
             name: if let Some(ident) = &field.ident {
                 syn::Member::Named(ident.clone())
             } else {
                 syn::Member::Unnamed(index.into())
             },
+            ty: field.ty.clone(),
             span: field.span(),
-            attrs: field.attrs.iter().into(),
-        }
+            attrs: FieldAttrs::from_attrs(field.attrs.iter())?,
+            serde_skip: field.attrs.iter().any(is_serde_skip),
+            cfgs: field
+                .attrs
+                .iter()
+                .filter(|attr| attr.path().is_ident("cfg"))
+                .cloned()
+                .collect(),
+            merge_attrs: field
+                .attrs
+                .iter()
+                .filter(|attr| attr.path().is_ident("merge"))
+                .cloned()
+                .collect(),
+        })
     }
 }
 
 impl FieldAttrs {
-    fn apply(&mut self, attr: FieldAttr) {
+    fn apply(&mut self, NamedAttr { name, attr }: NamedAttr) -> syn::Result<()> {
+        use syn::spanned::Spanned;
+
+        if matches!(
+            attr,
+            Attr::Strategy(_) | Attr::InnerStrategy(_) | Attr::Default(_)
+        ) {
+            match &self.strategy_key {
+                Some(key) if *key != name => bail!(
+                    name,
+                    "The `{}` and `{}` attributes are mutually exclusive, both set the merge strategy",
+                    key,
+                    name
+                ),
+                _ => self.strategy_key = Some(name.clone()),
+            }
+        }
         match attr {
-            FieldAttr::Skip => self.skip = true,
-            FieldAttr::Strategy(path) => self.strategy = Some(path),
+            Attr::Skip => self.skip = true,
+            Attr::Strategy(expr) => self.strategy = Some(expr),
+            Attr::Reverse => self.reverse = true,
+            Attr::InnerStrategy(expr) => {
+                self.strategy = Some(syn::parse_quote_spanned!(expr.span()=>
+                    ::merge2::option::zip_with(#expr)
+                ));
+            }
+            Attr::Default(expr) => {
+                self.strategy = Some(syn::parse_quote_spanned!(expr.span()=>
+                    |left, right| {
+                        if *left == (#expr) {
+                            ::core::mem::swap(left, right);
+                        }
+                    }
+                ));
+            }
+            _ => bail!(
+                name,
+                "The `{}` attribute can only be used on the struct, not on a field",
+                name
+            ),
         }
+        Ok(())
     }
-}
 
-impl<'a, I: Iterator<Item = &'a syn::Attribute>> From<I> for FieldAttrs {
-    fn from(iter: I) -> Self {
+    /// Collects the `merge` attributes in order, so a later `strategy` replaces an earlier one.
+    /// Different attributes that set the strategy can't be combined.
+    fn from_attrs<'a, I: Iterator<Item = &'a syn::Attribute>>(iter: I) -> syn::Result<Self> {
         let mut field_attrs = Self::default();
 
-        for attr in iter {
-            if !attr.path().is_ident("merge") {
-                continue;
+        for attr in iter.filter(|attr| attr.path().is_ident("merge")) {
+            for named in attr.parse_args_with(NamedAttr::parse_list)? {
+                field_attrs.apply(named)?;
+            }
+            if let (true, Some(key)) = (field_attrs.skip, &field_attrs.strategy_key) {
+                bail!(
+                    attr,
+                    "The `skip` and `{}` attributes are mutually exclusive",
+                    key
+                )
+            }
+            if field_attrs.skip && field_attrs.reverse {
+                bail!(
+                    attr,
+                    "The `skip` and `reverse` attributes are mutually exclusive"
+                )
             }
+        }
+
+        Ok(field_attrs)
+    }
+}
 
-            let parser = syn::punctuated::Punctuated::<FieldAttr, Token![,]>::parse_terminated;
-            for attr in attr.parse_args_with(parser).unwrap() {
-                field_attrs.apply(attr);
+impl ContainerAttrs {
+    fn apply(&mut self, NamedAttr { name, attr }: NamedAttr) -> syn::Result<()> {
+        match attr {
+            Attr::Strategy(expr) => self.strategy = Some(expr),
+            Attr::Post(path) => self.post = Some(path),
+            Attr::Bound(bound) => self.bound = Some(bound),
+            Attr::OptionRight(right) => self.option_right = right,
+            Attr::SkipDefaultOther => self.skip_default_other = true,
+            Attr::CloneRef => self.clone_ref = Some(name),
+            Attr::Transparent => self.transparent = true,
+            Attr::Doc(doc) => self.doc.push(doc),
+            Attr::RespectSerdeSkip => self.respect_serde_skip = true,
+            Attr::AllowNoop => self.allow_noop = true,
+            Attr::Skip | Attr::Reverse | Attr::InnerStrategy(_) | Attr::Default(_) => bail!(
+                name,
+                "The `{}` attribute can only be used on a field, not on the struct",
+                name
+            ),
+        }
+        Ok(())
+    }
+
+    fn from_attrs<'a, I: Iterator<Item = &'a syn::Attribute>>(iter: I) -> syn::Result<Self> {
+        let mut container = Self::default();
+
+        for attr in iter.filter(|attr| attr.path().is_ident("merge")) {
+            for named in attr.parse_args_with(NamedAttr::parse_list)? {
+                container.apply(named)?;
             }
         }
 
-        field_attrs
+        Ok(container)
+    }
+}
+
+impl NamedAttr {
+    fn parse_list(
+        input: syn::parse::ParseStream,
+    ) -> syn::parse::Result<syn::punctuated::Punctuated<Self, Token![,]>> {
+        syn::punctuated::Punctuated::parse_terminated(input)
     }
 }
 
-impl syn::parse::Parse for FieldAttr {
+impl syn::parse::Parse for NamedAttr {
     fn parse(input: syn::parse::ParseStream) -> syn::parse::Result<Self> {
         let name: syn::Ident = input.parse()?;
-        if name == "skip" {
+        let attr = if name == "skip" || name == "ignore" {
             // TODO check remaining stream
-            Ok(FieldAttr::Skip)
-        } else if name == "strategy" {
+            Attr::Skip
+        } else if name == "strategy" || name == "inner_strategy" {
+            let _: Token![=] = input.parse()?;
+            let expr: syn::Expr = input.parse()?;
+            // Strategies passed through `macro_rules!` fragments are wrapped in invisible groups
+            let mut inner = &expr;
+            while let syn::Expr::Group(group) = inner {
+                inner = &group.expr;
+            }
+            match inner {
+                syn::Expr::Path(_) | syn::Expr::Call(_) | syn::Expr::Closure(_) => {
+                    if name == "strategy" {
+                        Attr::Strategy(expr)
+                    } else {
+                        Attr::InnerStrategy(expr)
+                    }
+                }
+                _ => bail!(
+                    expr,
+                    "Expected a function path, a call or a closure as the merge strategy"
+                ),
+            }
+        } else if name == "default" {
+            let _: Token![=] = input.parse()?;
+            Attr::Default(input.parse()?)
+        } else if name == "post" {
+            let _: Token![=] = input.parse()?;
+            Attr::Post(input.parse()?)
+        } else if name == "bound" {
             let _: Token![=] = input.parse()?;
-            let path: syn::Path = input.parse()?;
-            Ok(FieldAttr::Strategy(path))
+            let lit: syn::LitStr = input.parse()?;
+            let parser =
+                syn::punctuated::Punctuated::<syn::WherePredicate, Token![,]>::parse_terminated;
+            let bound = lit.parse_with(parser)?;
+            Attr::Bound(bound.into_iter().collect())
+        } else if name == "option" {
+            let _: Token![=] = input.parse()?;
+            let side: syn::Ident = input.parse()?;
+            if side == "left" {
+                Attr::OptionRight(false)
+            } else if side == "right" {
+                Attr::OptionRight(true)
+            } else {
+                bail!(side, "Expected `left` or `right`, found: {}", side)
+            }
+        } else if name == "skip_default_other" {
+            Attr::SkipDefaultOther
+        } else if name == "clone_ref" {
+            Attr::CloneRef
+        } else if name == "transparent" {
+            Attr::Transparent
+        } else if name == "doc" {
+            let _: Token![=] = input.parse()?;
+            Attr::Doc(input.parse()?)
+        } else if name == "respect_serde_skip" {
+            Attr::RespectSerdeSkip
+        } else if name == "reverse" {
+            Attr::Reverse
+        } else if name == "allow_noop" {
+            Attr::AllowNoop
         } else {
             bail!(name, "Unexpected attribute: {}", name)
-        }
+        };
+        Ok(NamedAttr { name, attr })
     }
 }
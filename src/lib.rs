@@ -22,12 +22,26 @@
 //!
 //! This crate has the following features:
 //!
+//! - `alloc`: Enables the merge strategies in the `vec` module and the other ones that require
+//!   the `alloc` crate, without the standard library.
+//! - `bytes`: Enables the `Merge` implementations for `Bytes` and `BytesMut`, and the merge
+//!   strategies in the `bytes` module that require the `bytes` crate.
+//! - `chrono`: Enables the `Merge` implementation for `DateTime`, and the merge strategies in the
+//!   `chrono` module that require the `chrono` crate.
 //! - `derive` (default):  Enables the derive macro for the `Merge` trait using the `merge_derive`
 //!   crate.
 //! - `num`: Enables the merge strategies in the `num` module that require the
 //!   `num_traits` crate.
-//! - `std` (default): Enables the merge strategies in the `hashmap` and `vec` modules that require
-//!    the standard library.  If this feature is not set, `merge2` is a `no_std`.
+//! - `option-recursive-default`: Makes the `Merge` implementation of `Option<T>` require
+//!   `T: Merge` and merge the values if both are `Some`, like [`option::recursive`][]. It's off
+//!   by default, and enabling it is a breaking change: `Option<T>` fields with a `T` that doesn't
+//!   implement `Merge` stop compiling, and nested values are merged instead of keeping `left`.
+//!   Since Cargo unifies features, it also affects every other crate in the dependency graph
+//!   that uses `merge2`, so it should only be enabled by the final binary.
+//! - `std` (default): Enables the merge strategies in the `hashmap`, `hashset` and `string`
+//!   modules that require the standard library.  If this feature is not set, `merge2` is a
+//!   `no_std`.
+//! - `test-util`: Enables the `test_util` module with assertions for testing merge strategies.
 //!
 //! # Example
 //!
@@ -64,10 +78,14 @@
 //! ```
 //!
 //! [`Merge`]: trait.Merge.html
+//! [`option::recursive`]: option/fn.recursive.html
 //! [`args.rs`]: https://github.com/RoDmitry/merge2/blob/main/examples/args.rs
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 #[cfg(feature = "derive")]
 pub use merge2_derive::*;
 
@@ -78,13 +96,66 @@ pub use merge2_derive::*;
 /// `Merge` can be derived for structs if the `derive` feature is enabled.  The generated
 /// implementation calls the `merge` method for all fields, or the merge strategy function if set.
 /// You can use these field attributes to configure the generated implementation:
-/// - `skip`: Skip this field in the `merge` method.
+/// - `skip` (or `ignore`): Skip this field in the `merge` method.
 /// - `strategy = f`: Call `f(self.field, other.field)` instead of calling the `merge` function for
-///    this field.
+///   this field. `f` can be a path or any expression evaluating to a function or a closure, like
+///   `::merge2::option::zip_with(f)`.
+/// - `inner_strategy = f`: For `Option` fields, overwrite `self.field` if it is `None`, and call
+///   `f` with the values if both are `Some`. Same as `strategy = ::merge2::option::zip_with(f)`.
+/// - `default = expr`: Overwrite `self.field` with `other.field` if `self.field == expr`, like
+///   [`any::overwrite_default`][] with a custom default value, for example a `-1` sentinel.
+/// - `reverse`: Merge `self.field` into `other.field` and move the result to `self.field`, which
+///   swaps the sides of the merge, for example `append` becomes `prepend`.
+///
+/// A field can have multiple `merge` attributes. If `strategy` is set more than once, the last one
+/// is used. `strategy`, `inner_strategy` and `default` all set the strategy, so only one of them
+/// can be used on a field, and none of them can be combined with `skip`.
 ///
 /// You can also set a default strategy for all fields by setting the `strategy` attribute for the
 /// struct.
 ///
+/// The other attributes below can only be set on the struct. Using a field attribute on the
+/// struct, or a struct attribute on a field, is a compile error.
+///
+/// For generic structs, a `FieldType: Merge` bound is added for every field that uses a type
+/// parameter and is merged by `Merge`. Skipped fields and fields with a strategy add no bounds.
+/// The `bound = "T: Trait"` struct attribute replaces these bounds with the given predicates.
+///
+/// The `option = right` struct attribute makes all `Option` fields without a `strategy` attribute
+/// use [`option::overwrite_some`][], so `right` wins if it's `Some`. The default is `left`.
+///
+/// The `respect_serde_skip` struct attribute also skips the fields with a `#[serde(skip)]`
+/// attribute, unless they have a `strategy` attribute.
+///
+/// The `transparent` struct attribute states that a newtype struct merges exactly like its only
+/// field: the generated `merge` calls the `Merge` implementation of the field. It's rejected on
+/// structs with more or less than one field, on a field with `skip`, `reverse` or a strategy, and
+/// together with a struct `strategy`. The `option = right` struct attribute doesn't apply to it.
+///
+/// The `skip_default_other` struct attribute makes `merge` return early, without merging any
+/// fields, if `other` equals `Self::default()`. This adds a `Self: Default + PartialEq` bound to
+/// the `Merge` implementation and saves work when merging many layers that are mostly default.
+/// The early return skips the whole merge, so the `post` function isn't called either.
+///
+/// The `clone_ref` struct attribute generates an additional inherent method
+/// `merge_ref(&mut self, other: &Self)` for merging a shared `other`. It requires `Self: Clone`
+/// and clones the whole `other` before merging it. Without `Clone`, the derive fails at the
+/// `clone_ref` attribute, except for generic structs, where `merge_ref` is only available for the
+/// type arguments that make the struct `Clone`.
+///
+/// The `doc = "..."` struct attribute adds a doc comment to the generated `impl` block, for
+/// example to describe the merge semantics of the fields in one place. It can be repeated.
+///
+/// The `post = f` struct attribute calls `f(self, other)` after all fields have been merged, where
+/// `f` has the signature `fn(&mut Self, &mut Self)`. It can be used to reconcile fields that
+/// depend on each other.
+///
+/// If no field is merged and there is no `post` function, the generated `merge` does nothing,
+/// which is usually a mistake, so the derive emits a warning. The `allow_noop` struct attribute
+/// acknowledges it and silences the warning, for example for marker structs. Unit structs and
+/// structs with no fields, like `struct S;`, `struct S();` and `struct S {}`, are supported the
+/// same way.
+///
 /// # Examples
 ///
 /// Deriving `Merge` for a struct:
@@ -148,11 +219,229 @@ pub use merge2_derive::*;
 ///     option3: None,
 /// }, val);
 /// ```
+///
+/// Reconciling fields after the merge:
+///
+/// ```
+/// use merge2::Merge;
+///
+/// #[derive(Debug, PartialEq, Merge)]
+/// #[merge(post = fill_len)]
+/// struct S {
+///     name: Option<String>,
+///     len: Option<usize>,
+/// }
+///
+/// fn fill_len(left: &mut S, _: &mut S) {
+///     if left.len.is_none() {
+///         left.len = left.name.as_ref().map(String::len);
+///     }
+/// }
+///
+/// let mut val = S {
+///     name: None,
+///     len: None,
+/// };
+/// val.merge(&mut S {
+///     name: Some("name".to_owned()),
+///     len: None,
+/// });
+/// assert_eq!(S {
+///     name: Some("name".to_owned()),
+///     len: Some(4),
+/// }, val);
+/// ```
+///
+/// `Merge` can't be derived for enums yet. It can be implemented manually, for example merging
+/// the fields of equal variants and keeping `left` if the variants differ:
+///
+/// ```
+/// use merge2::Merge;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum E {
+///     A,
+///     B(Option<u32>),
+/// }
+///
+/// impl Merge for E {
+///     fn merge(&mut self, other: &mut Self) {
+///         match (self, other) {
+///             (E::B(left), E::B(right)) => left.merge(right),
+///             // Unit variants have nothing to merge, and different variants keep `left`
+///             _ => {}
+///         }
+///     }
+/// }
+///
+/// let mut val = E::A;
+/// val.merge(&mut E::A);
+/// assert_eq!(E::A, val);
+///
+/// val.merge(&mut E::B(Some(1)));
+/// assert_eq!(E::A, val);
+///
+/// let mut val = E::B(None);
+/// val.merge(&mut E::B(Some(1)));
+/// assert_eq!(E::B(Some(1)), val);
+/// ```
 pub trait Merge: Sized {
     /// Merge another object into this object.
     fn merge(&mut self, other: &mut Self);
 }
 
+/// An extension trait for merging multiple objects into one.
+///
+/// It's implemented for all types implementing [`Merge`][].
+pub trait MergeExt: Merge {
+    /// Merge every item of the iterator into this object, in iteration order.
+    ///
+    /// For fields that keep `left` (like `Option`), `self` takes precedence, then earlier items
+    /// win over later ones.
+    ///
+    /// ```
+    /// use merge2::{Merge, MergeExt};
+    ///
+    /// #[derive(Merge)]
+    /// struct S {
+    ///     option1: Option<usize>,
+    ///     option2: Option<usize>,
+    /// }
+    ///
+    /// let mut val = S {
+    ///     option1: None,
+    ///     option2: None,
+    /// };
+    /// let mut layers = [
+    ///     S { option1: Some(1), option2: None },
+    ///     S { option1: Some(2), option2: Some(2) },
+    /// ];
+    /// val.merge_iter(&mut layers);
+    /// assert_eq!(Some(1), val.option1);
+    /// assert_eq!(Some(2), val.option2);
+    /// ```
+    #[inline]
+    fn merge_iter<'a, I: IntoIterator<Item = &'a mut Self>>(&mut self, iter: I)
+    where
+        Self: 'a,
+    {
+        for item in iter {
+            self.merge(item);
+        }
+    }
+}
+
+impl<T: Merge> MergeExt for T {}
+
+/// Implements [`Merge`][] for a struct by listing its fields, without the derive macro.
+///
+/// Every listed field is merged with its `Merge` implementation, or with the strategy after `=>`.
+/// Fields that aren't listed are skipped. Tuple struct fields are listed by their index.
+///
+/// It's useful for structs that can't use the `derive` feature, like structs generated by other
+/// macros. The orphan rule still applies, so the struct must be defined in the same crate. To
+/// merge a struct of another crate, wrap it in a local newtype and implement `Merge` for that.
+///
+/// ```
+/// use merge2::Merge;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct S {
+///     option: Option<usize>,
+///     max: u8,
+///     name: &'static str,
+/// }
+///
+/// merge2::impl_merge!(S { option, max => ::merge2::ord::max });
+///
+/// let mut val = S { option: None, max: 1, name: "left" };
+/// val.merge(&mut S { option: Some(1), max: 2, name: "right" });
+/// assert_eq!(S { option: Some(1), max: 2, name: "left" }, val);
+/// ```
+#[macro_export]
+macro_rules! impl_merge {
+    ($ty:ty { $($field:tt $(=> $strategy:expr)?),* $(,)? }) => {
+        impl $crate::Merge for $ty {
+            fn merge(&mut self, other: &mut Self) {
+                $( $crate::impl_merge!(@field self, other, $field $(, $strategy)?); )*
+            }
+        }
+    };
+    (@field $left:ident, $right:ident, $field:tt) => {
+        $crate::Merge::merge(&mut $left.$field, &mut $right.$field)
+    };
+    (@field $left:ident, $right:ident, $field:tt, $strategy:expr) => {
+        ($strategy)(&mut $left.$field, &mut $right.$field)
+    };
+}
+
+/// A trait for values that carry a timestamp, used by [`any::overwrite_if_newer`][].
+pub trait Timestamped {
+    /// The timestamp of the value. Greater is newer.
+    fn timestamp(&self) -> u64;
+}
+
+/// Assertions for testing `Merge` implementations and merge strategies.
+///
+/// To test a strategy, derive `Merge` for a wrapper using it and pass sample values:
+///
+/// ```
+/// use merge2::{test_util, Merge};
+///
+/// #[derive(Clone, Debug, Default, PartialEq, Merge)]
+/// struct S(#[merge(strategy = ::merge2::vec::append)] Vec<u8>);
+///
+/// test_util::assert_identity_right(S(vec![1, 2]));
+/// ```
+///
+/// This module is only available if the `test-util` feature is enabled.
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util {
+    use super::Merge;
+    use core::fmt::Debug;
+
+    /// Assert that merging a value with itself doesn't change it.
+    #[track_caller]
+    pub fn assert_keep_left<T: Merge + Clone + PartialEq + Debug>(a: T) {
+        let mut left = a.clone();
+        left.merge(&mut a.clone());
+        assert_eq!(a, left, "merging a value with itself changed it");
+    }
+
+    /// Assert that merging a default value into a value doesn't change it.
+    #[track_caller]
+    pub fn assert_identity_right<T: Merge + Clone + Default + PartialEq + Debug>(a: T) {
+        let mut left = a.clone();
+        left.merge(&mut T::default());
+        assert_eq!(a, left, "merging a default value changed the value");
+    }
+}
+
+#[doc(hidden)]
+pub mod __private {
+    /// Used by the derive macro to check the strategy against the field type.
+    #[inline(always)]
+    pub fn strategy<T, F: Fn(&mut T, &mut T)>(f: F) -> impl Fn(&mut T, &mut T) {
+        f
+    }
+
+    /// Used by the derive macro to explain the `Clone` requirement of `clone_ref`.
+    #[diagnostic::on_unimplemented(
+        message = "`#[merge(clone_ref)]` requires `{Self}` to implement `Clone`",
+        label = "`{Self}` doesn't implement `Clone`",
+        note = "derive or implement `Clone` for `{Self}`, or remove `clone_ref`"
+    )]
+    pub trait CloneRef: Clone {}
+
+    impl<T: Clone> CloneRef for T {}
+
+    /// Used by the derive macro to clone `other` in `merge_ref`.
+    #[inline(always)]
+    pub fn clone_ref<T: CloneRef>(value: &T) -> T {
+        value.clone()
+    }
+}
+
 /// Merge strategies applicable to any types
 pub mod any {
     /// Overwrite `left` with `right` regardless of their values. Sets `right` to a Default value.
@@ -162,6 +451,13 @@ pub mod any {
         *left = core::mem::take(right);
     }
 
+    /// Overwrite `left` with a clone of `right` regardless of their values. Unlike `overwrite`,
+    /// `right` is kept intact, so `T` doesn't need to implement `Default`.
+    #[inline]
+    pub fn overwrite_clone<T: Clone>(left: &mut T, right: &mut T) {
+        left.clone_from(right);
+    }
+
     /// Overwrite `left` with `right` if the value of `left` is equal to the Default for the type.
     #[inline]
     pub fn overwrite_default<T: Default + PartialEq>(left: &mut T, right: &mut T) {
@@ -170,6 +466,83 @@ pub mod any {
         }
     }
 
+    /// Overwrite `left` with `right` if the value of `left` is equal to the Default for the type.
+    ///
+    /// This is the same as `overwrite_default`. When folding many values into one, for example
+    /// with [`MergeExt::merge_iter`](super::MergeExt::merge_iter), the first non-default value
+    /// wins, like the first `Some` for `Option`.
+    #[inline]
+    pub fn first_nondefault<T: Default + PartialEq>(left: &mut T, right: &mut T) {
+        overwrite_default(left, right);
+    }
+
+    /// Overwrite `left` with `right` if the value of `left` is equal to the Default for the type,
+    /// and the value of `right` is not. Unlike `overwrite_default`, a default `right` never
+    /// replaces `left`.
+    #[inline]
+    pub fn fill_default<T: Default + PartialEq>(left: &mut T, right: &mut T) {
+        let default = T::default();
+        if *left == default && *right != default {
+            core::mem::swap(left, right);
+        }
+    }
+
+    /// Overwrite `left` with `right` only if `left` is equal to the Default for the type and
+    /// `right` is not, so a default `right` never overwrites `left`.
+    ///
+    /// This is the same as `fill_default`.
+    #[inline]
+    pub fn prefer_nondefault<T: Default + PartialEq>(left: &mut T, right: &mut T) {
+        fill_default(left, right);
+    }
+
+    /// Overwrite `left` with `right` only if they are not equal.
+    ///
+    /// Useful for change-tracking wrappers, which mark a value dirty on every write, even if the
+    /// written value is the same.
+    #[inline]
+    pub fn overwrite_if_changed<T: PartialEq>(left: &mut T, right: &mut T) {
+        if *left != *right {
+            core::mem::swap(left, right);
+        }
+    }
+
+    /// Overwrite `left` with `right` if the timestamp of `right` is newer. Sets `right` to a Default
+    /// value if overwritten.
+    #[inline]
+    pub fn overwrite_if_newer<T: super::Timestamped + Default>(left: &mut T, right: &mut T) {
+        if right.timestamp() > left.timestamp() {
+            *left = core::mem::take(right);
+        }
+    }
+
+    /// Run strategy `a`, then strategy `b` on the same values.
+    #[inline]
+    pub fn sequence<T>(
+        a: impl Fn(&mut T, &mut T),
+        b: impl Fn(&mut T, &mut T),
+    ) -> impl Fn(&mut T, &mut T) {
+        move |left, right| {
+            a(left, right);
+            b(left, right);
+        }
+    }
+
+    /// Overwrite `left` with the box of `right` regardless of their values, swapping the boxes.
+    ///
+    /// It works for unsized values like `Box<dyn Trait>`, which can't be merged field by field
+    /// because their concrete types are unknown.
+    ///
+    /// This strategy is only available if the `alloc` feature is enabled.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn overwrite_box<T: ?Sized>(
+        left: &mut alloc::boxed::Box<T>,
+        right: &mut alloc::boxed::Box<T>,
+    ) {
+        core::mem::swap(left, right);
+    }
+
     /// Swap `left` and `right` regardless of their values.
     #[inline]
     pub fn swap<T>(left: &mut T, right: &mut T) {
@@ -177,6 +550,7 @@ pub mod any {
     }
 }
 
+#[cfg(not(feature = "option-recursive-default"))]
 impl<T> Merge for Option<T> {
     /// Overwrite `option` only if it is `None`
     #[inline]
@@ -187,8 +561,69 @@ impl<T> Merge for Option<T> {
     }
 }
 
+#[cfg(feature = "option-recursive-default")]
+impl<T: Merge> Merge for Option<T> {
+    /// Overwrite `option` if it is `None`, and merge the values if both are `Some`,
+    /// like `option::recursive`
+    #[inline]
+    fn merge(&mut self, right: &mut Self) {
+        option::recursive(self, right);
+    }
+}
+
 /// Merge strategies for `Option`
 pub mod option {
+    /// Overwrite `left` with `right` only if `right` is `Some`.
+    ///
+    /// In other words, this gives precedence to `right`.
+    #[inline]
+    pub fn overwrite_some<T>(left: &mut Option<T>, right: &mut Option<T>) {
+        if right.is_some() {
+            core::mem::swap(left, right);
+        }
+    }
+
+    /// Keep the only `Some` if exactly one side is `Some`. If both are `Some`, `left` is kept.
+    ///
+    /// This is the same as the default `Merge` implementation, the name states the intent for
+    /// mutually exclusive options. Use `try_exclusive` to report the conflict instead.
+    #[inline]
+    pub fn prefer_exclusive<T>(left: &mut Option<T>, right: &mut Option<T>) {
+        if left.is_none() {
+            core::mem::swap(left, right);
+        }
+    }
+
+    /// The error of `try_exclusive`: both sides are `Some`.
+    #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+    pub struct BothSome;
+
+    impl core::fmt::Display for BothSome {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            f.write_str("both options are `Some`, but they are mutually exclusive")
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl std::error::Error for BothSome {}
+
+    /// Keep the only `Some` if exactly one side is `Some`, like `prefer_exclusive`. If both are
+    /// `Some`, return `BothSome` and leave both sides untouched.
+    ///
+    /// It can't be used as a `strategy` because it returns a `Result`, call it manually instead,
+    /// for example from a custom `Merge` implementation.
+    #[inline]
+    pub fn try_exclusive<T>(left: &mut Option<T>, right: &mut Option<T>) -> Result<(), BothSome> {
+        match (&left, &right) {
+            (Some(_), Some(_)) => Err(BothSome),
+            (None, _) => {
+                core::mem::swap(left, right);
+                Ok(())
+            }
+            (Some(_), None) => Ok(()),
+        }
+    }
+
     /// On conflict, recursively merge the elements.
     #[inline]
     pub fn recursive<T: super::Merge>(left: &mut Option<T>, right: &mut Option<T>) {
@@ -200,6 +635,155 @@ pub mod option {
             core::mem::swap(left, right);
         }
     }
+
+    /// Overwrite `left` with `right` if `left` is `None`, otherwise recursively merge `right`
+    /// into the element of `left`, so `left` wins the conflicts inside of the element.
+    ///
+    /// This is the same as `recursive`, the name only spells out the direction.
+    #[inline]
+    pub fn fill_or_recursive<T: super::Merge>(left: &mut Option<T>, right: &mut Option<T>) {
+        recursive(left, right);
+    }
+
+    /// On conflict, recursively merge the boxed elements.
+    ///
+    /// This strategy is only available if the `alloc` feature is enabled.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn recursive_boxed<T: super::Merge>(
+        left: &mut Option<alloc::boxed::Box<T>>,
+        right: &mut Option<alloc::boxed::Box<T>>,
+    ) {
+        if let Some(original) = left {
+            if let Some(new) = right {
+                original.as_mut().merge(new.as_mut());
+            }
+        } else {
+            core::mem::swap(left, right);
+        }
+    }
+
+    /// On conflict, append the elements of the vector in `right` to the vector in `left`.
+    /// Otherwise overwrite `left` only if it is `None`.
+    ///
+    /// This strategy is only available if the `alloc` feature is enabled.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn vec_append<T>(
+        left: &mut Option<alloc::vec::Vec<T>>,
+        right: &mut Option<alloc::vec::Vec<T>>,
+    ) {
+        if let Some(original) = left {
+            if let Some(new) = right {
+                super::vec::append(original, new);
+            }
+        } else {
+            core::mem::swap(left, right);
+        }
+    }
+
+    /// Replace the vector in `left` with the vector in `right` if `right` is `Some`, so `right`
+    /// wins when both are `Some`. If `right` is `None`, `left` is kept.
+    ///
+    /// Unlike `vec_append`, a layer either inherits the whole list or replaces it. Same as
+    /// `overwrite_some`, the name states the intent for list fields.
+    ///
+    /// This strategy is only available if the `alloc` feature is enabled.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn replace_some<T>(
+        left: &mut Option<alloc::vec::Vec<T>>,
+        right: &mut Option<alloc::vec::Vec<T>>,
+    ) {
+        overwrite_some(left, right);
+    }
+
+    /// On conflict, append the string in `right` to the string in `left`, like `string::append`.
+    /// Otherwise overwrite `left` only if it is `None`.
+    ///
+    /// This strategy is only available if the `alloc` feature is enabled.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn string_append(
+        left: &mut Option<alloc::string::String>,
+        right: &mut Option<alloc::string::String>,
+    ) {
+        match (left.as_mut(), right.as_mut()) {
+            (Some(original), Some(new)) if !original.is_empty() => {
+                original.push_str(&core::mem::take(new));
+            }
+            (Some(original), Some(new)) => core::mem::swap(original, new),
+            (None, _) => core::mem::swap(left, right),
+            (Some(_), None) => {}
+        }
+    }
+
+    /// On conflict, merge the map in `right` into the map in `left` with `hashmap::recursive`.
+    /// Otherwise overwrite `left` only if it is `None`.
+    ///
+    /// Unlike `recursive`, this doesn't use the default `Merge` implementation of `HashMap`,
+    /// which only fills an empty map.
+    ///
+    /// This strategy is only available if the `std` feature is enabled.
+    #[cfg(feature = "std")]
+    pub fn map_recursive<K, V, S>(
+        left: &mut Option<std::collections::HashMap<K, V, S>>,
+        right: &mut Option<std::collections::HashMap<K, V, S>>,
+    ) where
+        K: Eq + std::hash::Hash,
+        V: super::Merge,
+        S: std::hash::BuildHasher,
+    {
+        if let Some(original) = left {
+            if let Some(new) = right {
+                super::hashmap::recursive(original, new);
+            }
+        } else {
+            core::mem::swap(left, right);
+        }
+    }
+
+    /// On conflict, combine the elements with `f`. Otherwise overwrite `left` only if it is `None`.
+    ///
+    /// Unlike `recursive`, `T` doesn't need to implement `Merge`.
+    #[inline]
+    pub fn zip_with<T, F: Fn(&mut T, &mut T)>(f: F) -> impl Fn(&mut Option<T>, &mut Option<T>) {
+        move |left, right| {
+            if let Some(original) = left {
+                if let Some(new) = right {
+                    f(original, new);
+                }
+            } else {
+                core::mem::swap(left, right);
+            }
+        }
+    }
+}
+
+/// Adds the values, saturating at the numeric bounds.
+impl<T: Copy> Merge for core::num::Saturating<T>
+where
+    Self: core::ops::Add<Output = Self>,
+{
+    #[inline]
+    fn merge(&mut self, right: &mut Self) {
+        *self = *self + *right;
+    }
+}
+
+/// `ControlFlow` values can't be merged, `left` is kept.
+impl<B, C> Merge for core::ops::ControlFlow<B, C> {
+    #[inline(always)]
+    fn merge(&mut self, _: &mut Self) {}
+}
+
+/// `Infallible` has no values, so this implementation can never be called. It only exists to
+/// satisfy `Merge` bounds of generic types instantiated with `Infallible`.
+impl Merge for core::convert::Infallible {
+    #[inline(always)]
+    fn merge(&mut self, _: &mut Self) {
+        match *self {}
+    }
 }
 
 macro_rules! skip_merge {
@@ -211,9 +795,44 @@ macro_rules! skip_merge {
     )*};
 }
 
-skip_merge!(u8 i8 u16 i16 u32 i32 usize isize u64 i64 u128 i128 f32 f64 bool);
+skip_merge!(u8 i8 u16 i16 u32 i32 usize isize u64 i64 u128 i128 f32 f64 bool char);
+
+// Network addresses keep `left`, use `any::overwrite_clone` or `any::swap` to overwrite them
+#[cfg(feature = "std")]
+skip_merge!(
+    std::net::IpAddr std::net::Ipv4Addr std::net::Ipv6Addr
+    std::net::SocketAddr std::net::SocketAddrV4 std::net::SocketAddrV6
+);
 
 /// Merge strategies for boolean types.
+///
+/// Strategies only see a single field. To aggregate a flag into another field, use the `post`
+/// struct attribute, which has access to both structs:
+///
+/// ```
+/// use merge2::Merge;
+///
+/// #[derive(Merge)]
+/// #[merge(post = count_enabled)]
+/// struct S {
+///     #[merge(skip)]
+///     enabled: bool,
+///     #[merge(skip)]
+///     enabled_count: u32,
+/// }
+///
+/// fn count_enabled(left: &mut S, right: &mut S) {
+///     if right.enabled {
+///         left.enabled_count += 1;
+///     }
+/// }
+///
+/// let mut val = S { enabled: false, enabled_count: 0 };
+/// val.merge(&mut S { enabled: true, enabled_count: 0 });
+/// val.merge(&mut S { enabled: false, enabled_count: 0 });
+/// val.merge(&mut S { enabled: true, enabled_count: 0 });
+/// assert_eq!(2, val.enabled_count);
+/// ```
 pub mod bool {
     /// Overwrite left with right if the value of left is false.
     #[inline]
@@ -232,6 +851,19 @@ pub mod bool {
     }
 }
 
+/// Merge strategies for `char`.
+///
+/// The default value of `char` is `'\0'`, which is treated as unset.
+pub mod char {
+    /// Overwrite left with right if the value of left is `'\0'`.
+    #[inline]
+    pub fn overwrite_default(left: &mut char, right: &mut char) {
+        if *left == char::default() {
+            *left = *right;
+        }
+    }
+}
+
 /// Merge strategies for numeric types.
 ///
 /// These strategies are only available if the `num` feature is enabled.
@@ -244,19 +876,157 @@ pub mod num {
     }
 }
 
-/// Merge strategies for types that form a total order.
-pub mod ord {
-    use core::cmp;
+/// Merge strategies combining bit flags, like integers or other types implementing the bitwise
+/// operators.
+pub mod bits {
+    use core::ops::{BitAndAssign, BitOrAssign, BitXorAssign};
 
-    /// Swap elements if `left` is Less than `right`.
+    /// Set left to the bitwise or of left and right, keeping the flags set on any side.
     #[inline]
-    pub fn max<T: cmp::PartialOrd>(left: &mut T, right: &mut T) {
-        if cmp::PartialOrd::partial_cmp(left, right) == Some(cmp::Ordering::Less) {
-            core::mem::swap(left, right);
-        }
+    pub fn bitor<T: BitOrAssign + Copy>(left: &mut T, right: &mut T) {
+        *left |= *right;
     }
 
-    /// Swap elements if `left` is Greater than `right`.
+    /// Set left to the bitwise and of left and right, keeping the flags set on both sides.
+    #[inline]
+    pub fn bitand<T: BitAndAssign + Copy>(left: &mut T, right: &mut T) {
+        *left &= *right;
+    }
+
+    /// Set left to the bitwise xor of left and right, toggling the flags set on right.
+    #[inline]
+    pub fn bitxor<T: BitXorAssign + Copy>(left: &mut T, right: &mut T) {
+        *left ^= *right;
+    }
+}
+
+/// Merge strategies for durations.
+pub mod duration {
+    use core::time::Duration;
+
+    /// Set left to the sum of left and right, saturating at `Duration::MAX` instead of panicking
+    /// on overflow.
+    #[inline]
+    pub fn saturating_add(left: &mut Duration, right: &mut Duration) {
+        *left = left.saturating_add(*right);
+    }
+
+    /// Set left to the longer duration of left and right.
+    #[inline]
+    pub fn max(left: &mut Duration, right: &mut Duration) {
+        *left = (*left).max(*right);
+    }
+
+    /// Set left to the shorter duration of left and right.
+    #[inline]
+    pub fn min(left: &mut Duration, right: &mut Duration) {
+        *left = (*left).min(*right);
+    }
+}
+
+/// Merge strategies for floating point types that handle NaN explicitly.
+///
+/// Comparing with NaN through `PartialOrd` returns `None`, so `ord::max` and `ord::min` keep
+/// `left` whenever one of the values is NaN. These strategies define the NaN behavior instead.
+pub mod float {
+    mod private {
+        pub trait Sealed {}
+    }
+
+    /// Floating point types: `f32` and `f64`.
+    pub trait Float: Copy + PartialOrd + core::ops::Add<Output = Self> + private::Sealed {
+        /// Returns `true` if this value is NaN.
+        fn is_nan(self) -> bool;
+
+        /// Returns the ordering between `self` and `other` according to IEEE 754 `totalOrder`.
+        fn total_cmp(&self, other: &Self) -> core::cmp::Ordering;
+    }
+
+    macro_rules! impl_float {
+        ($($t:ty)*) => {$(
+            impl private::Sealed for $t {}
+
+            impl Float for $t {
+                #[inline(always)]
+                fn is_nan(self) -> bool {
+                    <$t>::is_nan(self)
+                }
+
+                #[inline(always)]
+                fn total_cmp(&self, other: &Self) -> core::cmp::Ordering {
+                    <$t>::total_cmp(self, other)
+                }
+            }
+        )*};
+    }
+
+    impl_float!(f32 f64);
+
+    /// Set left to the maximum of left and right. NaN is ignored: if one value is NaN, the other
+    /// one is kept. The result is NaN only if both are NaN.
+    #[inline]
+    pub fn max<T: Float>(left: &mut T, right: &mut T) {
+        if left.is_nan() || *right > *left {
+            *left = *right;
+        }
+    }
+
+    /// Set left to the minimum of left and right. NaN is ignored: if one value is NaN, the other
+    /// one is kept. The result is NaN only if both are NaN.
+    #[inline]
+    pub fn min<T: Float>(left: &mut T, right: &mut T) {
+        if left.is_nan() || *right < *left {
+            *left = *right;
+        }
+    }
+
+    /// Swap elements if `left` is Less than `right` according to `total_cmp`. Positive NaN is
+    /// greater than any other value and negative NaN is less, so the result is deterministic.
+    #[inline]
+    pub fn max_swap_total<T: Float>(left: &mut T, right: &mut T) {
+        if left.total_cmp(right) == core::cmp::Ordering::Less {
+            core::mem::swap(left, right);
+        }
+    }
+
+    /// Swap elements if `left` is Greater than `right` according to `total_cmp`. Positive NaN is
+    /// greater than any other value and negative NaN is less, so the result is deterministic.
+    #[inline]
+    pub fn min_swap_total<T: Float>(left: &mut T, right: &mut T) {
+        if left.total_cmp(right) == core::cmp::Ordering::Greater {
+            core::mem::swap(left, right);
+        }
+    }
+
+    /// Set left to the sum of left and right. NaN propagates: if one value is NaN, the result is
+    /// NaN.
+    #[inline]
+    pub fn add<T: Float>(left: &mut T, right: &mut T) {
+        *left = *left + *right;
+    }
+
+    /// Overwrite left with right if left is NaN.
+    #[inline]
+    pub fn overwrite_nan<T: Float>(left: &mut T, right: &mut T) {
+        if left.is_nan() {
+            *left = *right;
+        }
+    }
+}
+
+/// Merge strategies for types that form a total order.
+pub mod ord {
+    use core::cmp;
+
+    /// Swap elements if `left` is Less than `right`.
+    #[inline]
+    pub fn max<T: cmp::PartialOrd>(left: &mut T, right: &mut T) {
+        if cmp::PartialOrd::partial_cmp(left, right) == Some(cmp::Ordering::Less) {
+            core::mem::swap(left, right);
+        }
+    }
+
+    /// Swap elements if `left` is Greater than `right`.
     #[inline]
     pub fn min<T: cmp::PartialOrd>(left: &mut T, right: &mut T) {
         if cmp::PartialOrd::partial_cmp(left, right) == Some(cmp::Ordering::Greater) {
@@ -265,6 +1035,108 @@ pub mod ord {
     }
 }
 
+/// Wrapper types with custom `Merge` implementations.
+pub mod wrappers {
+    /// A value with bounds, the bounds of `right` are applied on merge.
+    #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+    pub struct Clamped<T> {
+        /// The clamped value.
+        pub value: T,
+        /// The lower bound.
+        pub lo: T,
+        /// The upper bound.
+        pub hi: T,
+    }
+
+    impl<T> Clamped<T> {
+        /// Create a value with bounds. The value is not clamped until merged.
+        #[inline]
+        pub const fn new(value: T, lo: T, hi: T) -> Self {
+            Self { value, lo, hi }
+        }
+    }
+
+    impl<T: Ord + Clone> super::Merge for Clamped<T> {
+        /// Take the bounds of `right` and clamp the value of `left` into them.
+        /// If `lo` is greater than `hi`, the value is set to `hi`.
+        #[inline]
+        fn merge(&mut self, right: &mut Self) {
+            core::mem::swap(&mut self.lo, &mut right.lo);
+            core::mem::swap(&mut self.hi, &mut right.hi);
+            if self.value < self.lo {
+                self.value.clone_from(&self.lo);
+            }
+            if self.value > self.hi {
+                self.value.clone_from(&self.hi);
+            }
+        }
+    }
+}
+
+/// Merges the elements pairwise.
+impl<T: Merge, const N: usize> Merge for [T; N] {
+    #[inline]
+    fn merge(&mut self, right: &mut Self) {
+        array::recursive(self, right);
+    }
+}
+
+macro_rules! impl_tuple {
+    ($($name:ident $index:tt)*) => {
+        /// Merges the elements pairwise. Implemented for tuples with up to 16 elements.
+        impl<$($name: Merge),*> Merge for ($($name,)*) {
+            #[inline]
+            fn merge(&mut self, _right: &mut Self) {
+                $( self.$index.merge(&mut _right.$index); )*
+            }
+        }
+    };
+}
+
+impl_tuple!();
+impl_tuple!(A 0);
+impl_tuple!(A 0 B 1);
+impl_tuple!(A 0 B 1 C 2);
+impl_tuple!(A 0 B 1 C 2 D 3);
+impl_tuple!(A 0 B 1 C 2 D 3 E 4);
+impl_tuple!(A 0 B 1 C 2 D 3 E 4 F 5);
+impl_tuple!(A 0 B 1 C 2 D 3 E 4 F 5 G 6);
+impl_tuple!(A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7);
+impl_tuple!(A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7 I 8);
+impl_tuple!(A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7 I 8 J 9);
+impl_tuple!(A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7 I 8 J 9 K 10);
+impl_tuple!(A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7 I 8 J 9 K 10 L 11);
+impl_tuple!(A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7 I 8 J 9 K 10 L 11 M 12);
+impl_tuple!(A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7 I 8 J 9 K 10 L 11 M 12 N 13);
+impl_tuple!(A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7 I 8 J 9 K 10 L 11 M 12 N 13 O 14);
+impl_tuple!(A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7 I 8 J 9 K 10 L 11 M 12 N 13 O 14 P 15);
+
+/// Merge strategies for arrays.
+///
+/// Unlike vectors, arrays have a fixed length, so there are no strategies appending elements.
+pub mod array {
+    /// Recursively merge the elements of right into the elements of left with the same index.
+    /// Same as the default `Merge` implementation.
+    #[inline]
+    pub fn recursive<T: super::Merge, const N: usize>(left: &mut [T; N], right: &mut [T; N]) {
+        for (left, right) in left.iter_mut().zip(right.iter_mut()) {
+            left.merge(right);
+        }
+    }
+
+    /// Overwrite left with right if all elements of left are equal to the Default for the type.
+    #[inline]
+    pub fn overwrite_if_all_default<T: Default + PartialEq, const N: usize>(
+        left: &mut [T; N],
+        right: &mut [T; N],
+    ) {
+        let default = T::default();
+        if left.iter().all(|item| *item == default) {
+            core::mem::swap(left, right);
+        }
+    }
+}
+
 #[cfg(feature = "std")]
 impl Merge for &str {
     #[inline]
@@ -290,6 +1162,22 @@ impl Merge for String {
 /// These strategies are only available if the `std` feature is enabled.
 #[cfg(feature = "std")]
 pub mod string {
+    /// Overwrite left with right if left is empty. Same as the default `Merge` implementation.
+    #[inline]
+    pub fn overwrite_if_empty(left: &mut String, right: &mut String) {
+        if left.is_empty() {
+            core::mem::swap(left, right);
+        }
+    }
+
+    /// Overwrite left with right if left is empty or contains only whitespace.
+    #[inline]
+    pub fn overwrite_if_blank(left: &mut String, right: &mut String) {
+        if left.trim().is_empty() {
+            core::mem::swap(left, right);
+        }
+    }
+
     /// Append the contents of right to left.
     #[inline]
     pub fn append(left: &mut String, right: &mut String) {
@@ -311,10 +1199,48 @@ pub mod string {
             *left = core::mem::take(right);
         }
     }
+
+    /// Truncate left to at most the length of right in bytes, so right acts as a cap.
+    ///
+    /// If the cap falls inside of a multibyte character, left is truncated before that character,
+    /// so it can end up shorter than right. Right is kept unchanged.
+    #[inline]
+    #[allow(clippy::ptr_arg)]
+    pub fn clamp_len(left: &mut String, right: &mut String) {
+        let mut len = right.len();
+        if left.len() > len {
+            while !left.is_char_boundary(len) {
+                len -= 1;
+            }
+            left.truncate(len);
+        }
+    }
+
+    /// Append the lines of right that are not present in left, preserving their order.
+    ///
+    /// Lines are split on `\n` and compared without a trailing `\r`, so LF and CRLF lines are
+    /// equal. Appended lines end with `\n`, and a `\n` is added to the end of left first if it's
+    /// missing. If no lines are appended, left stays unchanged.
+    pub fn append_lines_unique(left: &mut String, right: &mut String) {
+        let mut seen: std::collections::HashSet<&str> = left.lines().collect();
+        let new: Vec<&str> = right.lines().filter(|line| seen.insert(line)).collect();
+        drop(seen);
+
+        if !new.is_empty() {
+            if !left.is_empty() && !left.ends_with('\n') {
+                left.push('\n');
+            }
+            for line in new {
+                left.push_str(line);
+                left.push('\n');
+            }
+        }
+        right.clear();
+    }
 }
 
-#[cfg(feature = "std")]
-impl<T> Merge for Vec<T> {
+#[cfg(feature = "alloc")]
+impl<T> Merge for alloc::vec::Vec<T> {
     #[inline]
     fn merge(&mut self, right: &mut Self) {
         if self.is_empty() {
@@ -325,19 +1251,42 @@ impl<T> Merge for Vec<T> {
 
 /// Merge strategies for vectors.
 ///
-/// These strategies are only available if the `std` feature is enabled.
-#[cfg(feature = "std")]
+/// These strategies are only available if the `alloc` feature is enabled.
+#[cfg(feature = "alloc")]
 pub mod vec {
+    use alloc::vec::Vec;
+
+    /// Overwrite left with right if left is empty. Same as the default `Merge` implementation.
+    #[inline]
+    pub fn overwrite_if_empty<T>(left: &mut Vec<T>, right: &mut Vec<T>) {
+        if left.is_empty() {
+            core::mem::swap(left, right);
+        }
+    }
+
     /// Append the contents of right to left.
+    ///
+    /// The space for `right` is reserved before appending, so `left` grows at most once.
     #[inline]
     pub fn append<T>(left: &mut Vec<T>, right: &mut Vec<T>) {
         if left.is_empty() {
             core::mem::swap(left, right);
         } else {
+            left.reserve(right.len());
             left.append(right);
         }
     }
 
+    /// Append the copied contents of right to left, then clear right.
+    ///
+    /// Unlike `append`, this never swaps the buffers if `left` is empty, so both sides keep their
+    /// allocations. Prefer it when `right` is a buffer that is refilled and merged repeatedly.
+    #[inline]
+    pub fn append_copy<T: Copy>(left: &mut Vec<T>, right: &mut Vec<T>) {
+        left.extend_from_slice(right);
+        right.clear();
+    }
+
     /// Prepend the contents of right to left.
     #[inline]
     pub fn prepend<T>(left: &mut Vec<T>, right: &mut Vec<T>) {
@@ -348,12 +1297,291 @@ pub mod vec {
             core::mem::swap(left, right);
         }
     }
+
+    /// Truncate left to at most the length of right, so right acts as a cap. It never grows left,
+    /// and right is kept unchanged.
+    #[inline]
+    #[allow(clippy::ptr_arg)]
+    pub fn clamp_len<T>(left: &mut Vec<T>, right: &mut Vec<T>) {
+        left.truncate(right.len());
+    }
+
+    /// Interleave the elements of left and right, starting with left: `[l0, r0, l1, r1, ...]`.
+    ///
+    /// If one side is longer, its remaining elements are appended in order. Right is drained.
+    pub fn interleave<T>(left: &mut Vec<T>, right: &mut Vec<T>) {
+        if right.is_empty() {
+            return;
+        }
+        let mut old = core::mem::take(left).into_iter();
+        let mut new = right.drain(..);
+        left.reserve(old.len() + new.len());
+        loop {
+            match (old.next(), new.next()) {
+                (None, None) => break,
+                (l, r) => {
+                    left.extend(l);
+                    left.extend(r);
+                }
+            }
+        }
+    }
+
+    /// Merge two sorted vectors without duplicates into one sorted vector without duplicates,
+    /// like a union of sorted sets. Of two equal elements, the one of left is kept. Right is
+    /// drained.
+    ///
+    /// Both sides must already be sorted and deduplicated, otherwise the result is unspecified.
+    pub fn union_sorted<T: Ord>(left: &mut Vec<T>, right: &mut Vec<T>) {
+        use core::cmp::Ordering;
+
+        if right.is_empty() {
+            return;
+        }
+        let mut old = core::mem::take(left).into_iter().peekable();
+        let mut new = right.drain(..).peekable();
+        left.reserve(old.len() + new.len());
+        loop {
+            let ordering = match (old.peek(), new.peek()) {
+                (Some(l), Some(r)) => l.cmp(r),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => break,
+            };
+            match ordering {
+                Ordering::Less => left.extend(old.next()),
+                Ordering::Greater => left.extend(new.next()),
+                Ordering::Equal => {
+                    left.extend(old.next());
+                    new.next();
+                }
+            }
+        }
+    }
+
+    /// Merge association lists: the value of each pair in right is merged into the value of the
+    /// pair in left with the same key, other pairs are appended in order.
+    ///
+    /// Keys are matched by searching `left`, which takes O(left.len() * right.len()).
+    pub fn merge_assoc<K: Eq, V: super::Merge>(left: &mut Vec<(K, V)>, right: &mut Vec<(K, V)>) {
+        for (k, mut v) in right.drain(..) {
+            if let Some((_, original)) = left.iter_mut().find(|(key, _)| *key == k) {
+                original.merge(&mut v);
+            } else {
+                left.push((k, v));
+            }
+        }
+    }
+
+    /// Merge the elements of right into the elements of left with the same key, append the rest.
+    ///
+    /// Elements are matched by searching `left`, which takes O(left.len() * right.len()).
+    pub fn merge_by_key<T: super::Merge, K: Eq, F: Fn(&T) -> K>(
+        key: F,
+    ) -> impl Fn(&mut Vec<T>, &mut Vec<T>) {
+        move |left, right| {
+            for mut new in right.drain(..) {
+                let k = key(&new);
+                if let Some(original) = left.iter_mut().find(|item| key(item) == k) {
+                    original.merge(&mut new);
+                } else {
+                    left.push(new);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Merge for alloc::boxed::Box<[T]> {
+    #[inline]
+    fn merge(&mut self, right: &mut Self) {
+        if self.is_empty() {
+            core::mem::swap(self, right);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Merge for alloc::rc::Rc<[T]> {
+    #[inline]
+    fn merge(&mut self, right: &mut Self) {
+        if self.is_empty() {
+            core::mem::swap(self, right);
+        }
+    }
+}
+
+/// Merge strategies for boxed slices.
+///
+/// These strategies are only available if the `alloc` feature is enabled.
+#[cfg(feature = "alloc")]
+pub mod boxed_slice {
+    use alloc::{boxed::Box, vec::Vec};
+
+    /// Append the contents of right to left.
+    ///
+    /// A boxed slice can't grow, so unless one of the sides is empty, this allocates a new slice
+    /// with the length of both sides and moves all elements into it.
+    pub fn append<T>(left: &mut Box<[T]>, right: &mut Box<[T]>) {
+        if left.is_empty() {
+            core::mem::swap(left, right);
+        } else if !right.is_empty() {
+            let mut vec = Vec::from(core::mem::take(left));
+            vec.append(&mut Vec::from(core::mem::take(right)));
+            *left = vec.into_boxed_slice();
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Clone> Merge for alloc::borrow::Cow<'_, [T]> {
+    #[inline]
+    fn merge(&mut self, right: &mut Self) {
+        if self.is_empty() {
+            core::mem::swap(self, right);
+        }
+    }
+}
+
+/// Merge strategies for clone-on-write slices.
+///
+/// These strategies are only available if the `alloc` feature is enabled.
+#[cfg(feature = "alloc")]
+pub mod cow {
+    use alloc::borrow::Cow;
+
+    /// Append the contents of right to left.
+    ///
+    /// If left is borrowed, it's cloned into an owned vector first. The elements of right are
+    /// moved if it's owned, and cloned if it's borrowed. Nothing is cloned if either side is empty.
+    pub fn append<'a, T: Clone>(left: &mut Cow<'a, [T]>, right: &mut Cow<'a, [T]>) {
+        if left.is_empty() {
+            core::mem::swap(left, right);
+        } else if !right.is_empty() {
+            match core::mem::take(right) {
+                Cow::Borrowed(slice) => left.to_mut().extend_from_slice(slice),
+                Cow::Owned(mut vec) => left.to_mut().append(&mut vec),
+            }
+        }
+    }
+}
+
+/// `Weak` references can't be merged, `left` is kept.
+#[cfg(feature = "alloc")]
+impl<T> Merge for alloc::rc::Weak<T> {
+    #[inline(always)]
+    fn merge(&mut self, _: &mut Self) {}
+}
+
+/// `Weak` references can't be merged, `left` is kept.
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+impl<T> Merge for alloc::sync::Weak<T> {
+    #[inline(always)]
+    fn merge(&mut self, _: &mut Self) {}
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord> Merge for alloc::collections::BinaryHeap<T> {
+    #[inline]
+    fn merge(&mut self, right: &mut Self) {
+        if self.is_empty() {
+            core::mem::swap(self, right);
+        }
+    }
+}
+
+/// Merge strategies for binary heaps.
+///
+/// These strategies are only available if the `alloc` feature is enabled.
+#[cfg(feature = "alloc")]
+pub mod binary_heap {
+    use alloc::collections::BinaryHeap;
+
+    /// Move all elements of right into left.
+    #[inline]
+    pub fn append<T: Ord>(left: &mut BinaryHeap<T>, right: &mut BinaryHeap<T>) {
+        left.append(right);
+    }
+}
+
+/// A path is empty if `as_os_str().is_empty()` is true, an empty `left` is swapped with `right`.
+#[cfg(feature = "std")]
+impl Merge for std::borrow::Cow<'_, std::path::Path> {
+    #[inline]
+    fn merge(&mut self, right: &mut Self) {
+        if self.as_os_str().is_empty() {
+            core::mem::swap(self, right);
+        }
+    }
+}
+
+/// Shared paths are immutable, `left` is kept.
+#[cfg(feature = "std")]
+impl Merge for std::sync::Arc<std::path::Path> {
+    #[inline(always)]
+    fn merge(&mut self, _: &mut Self) {}
+}
+
+/// Shared paths are immutable, `left` is kept.
+#[cfg(feature = "std")]
+impl Merge for std::rc::Rc<std::path::Path> {
+    #[inline(always)]
+    fn merge(&mut self, _: &mut Self) {}
+}
+
+/// Merges the values through `get_mut`, which doesn't lock because both sides are borrowed
+/// mutably. A poisoned mutex is recovered with `PoisonError::into_inner` and merged as usual.
+#[cfg(feature = "std")]
+impl<T: Merge> Merge for std::sync::Mutex<T> {
+    #[inline]
+    fn merge(&mut self, right: &mut Self) {
+        use std::sync::PoisonError;
+
+        let left = self.get_mut().unwrap_or_else(PoisonError::into_inner);
+        left.merge(right.get_mut().unwrap_or_else(PoisonError::into_inner));
+    }
+}
+
+/// Merges the values through `get_mut`, which doesn't lock because both sides are borrowed
+/// mutably. A poisoned lock is recovered with `PoisonError::into_inner` and merged as usual.
+#[cfg(feature = "std")]
+impl<T: Merge> Merge for std::sync::RwLock<T> {
+    #[inline]
+    fn merge(&mut self, right: &mut Self) {
+        use std::sync::PoisonError;
+
+        let left = self.get_mut().unwrap_or_else(PoisonError::into_inner);
+        left.merge(right.get_mut().unwrap_or_else(PoisonError::into_inner));
+    }
+}
+
+/// Merge strategies for shared state.
+///
+/// These strategies are only available if the `std` feature is enabled.
+#[cfg(feature = "std")]
+pub mod sync {
+    use std::sync::{Arc, Mutex, PoisonError};
+
+    /// Lock both mutexes and merge the value of `right` into the value of `left`.
+    ///
+    /// `left` is always locked before `right`, so merging the same pair concurrently in opposite
+    /// directions can deadlock. If both `Arc`s point to the same mutex, nothing is done.
+    /// A poisoned mutex is recovered and merged as usual.
+    pub fn mutex_merge<T: super::Merge>(left: &mut Arc<Mutex<T>>, right: &mut Arc<Mutex<T>>) {
+        if Arc::ptr_eq(left, right) {
+            return;
+        }
+        let mut left = left.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut right = right.lock().unwrap_or_else(PoisonError::into_inner);
+        left.merge(&mut right);
+    }
 }
 
 #[cfg(feature = "std")]
 use std::collections::HashMap;
 #[cfg(feature = "std")]
-impl<K, V> Merge for HashMap<K, V> {
+impl<K, V, S> Merge for HashMap<K, V, S> {
     #[inline]
     fn merge(&mut self, right: &mut Self) {
         if self.is_empty() {
@@ -364,19 +1592,24 @@ impl<K, V> Merge for HashMap<K, V> {
 
 /// Merge strategies for hash maps.
 ///
+/// The strategies work with any hasher `S: BuildHasher`. They drain `right` instead of replacing
+/// it, so `S` doesn't need to implement `Default`.
+///
 /// These strategies are only available if the `std` feature is enabled.
 #[cfg(feature = "std")]
 pub mod hashmap {
     use super::HashMap;
-    use std::hash::Hash;
+    use std::hash::{BuildHasher, Hash};
 
     /// On conflict, merge elements from `right` to `left`.
     ///
     /// In other words, this gives precedence to `left`.
     #[inline]
-    pub fn merge<K: Eq + Hash, V>(left: &mut HashMap<K, V>, right: &mut HashMap<K, V>) {
-        let map = core::mem::take(right);
-        for (k, v) in map {
+    pub fn merge<K: Eq + Hash, V, S: BuildHasher>(
+        left: &mut HashMap<K, V, S>,
+        right: &mut HashMap<K, V, S>,
+    ) {
+        for (k, v) in right.drain() {
             left.entry(k).or_insert(v);
         }
     }
@@ -385,19 +1618,21 @@ pub mod hashmap {
     ///
     /// In other words, this gives precedence to `right`.
     #[inline]
-    pub fn replace<K: Eq + Hash, V>(left: &mut HashMap<K, V>, right: &mut HashMap<K, V>) {
-        left.extend(core::mem::take(right))
+    pub fn replace<K: Eq + Hash, V, S: BuildHasher>(
+        left: &mut HashMap<K, V, S>,
+        right: &mut HashMap<K, V, S>,
+    ) {
+        left.extend(right.drain())
     }
 
     /// On conflict, recursively merge the elements.
-    pub fn recursive<K: Eq + Hash, V: super::Merge>(
-        left: &mut HashMap<K, V>,
-        right: &mut HashMap<K, V>,
+    pub fn recursive<K: Eq + Hash, V: super::Merge, S: BuildHasher>(
+        left: &mut HashMap<K, V, S>,
+        right: &mut HashMap<K, V, S>,
     ) {
         use std::collections::hash_map::Entry;
 
-        let map = core::mem::take(right);
-        for (k, mut v) in map {
+        for (k, mut v) in right.drain() {
             match left.entry(k) {
                 Entry::Occupied(mut existing) => existing.get_mut().merge(&mut v),
                 Entry::Vacant(empty) => {
@@ -407,18 +1642,523 @@ pub mod hashmap {
         }
     }
 
+    /// On conflict, merge the nested maps with `recursive` instead of their default `Merge`
+    /// implementation, which would only fill an empty map.
+    pub fn recursive_deep<K, K2, V, S, S2>(
+        left: &mut HashMap<K, HashMap<K2, V, S2>, S>,
+        right: &mut HashMap<K, HashMap<K2, V, S2>, S>,
+    ) where
+        K: Eq + Hash,
+        K2: Eq + Hash,
+        V: super::Merge,
+        S: BuildHasher,
+        S2: BuildHasher,
+    {
+        use std::collections::hash_map::Entry;
+
+        for (k, mut v) in right.drain() {
+            match left.entry(k) {
+                Entry::Occupied(mut existing) => recursive(existing.get_mut(), &mut v),
+                Entry::Vacant(empty) => {
+                    empty.insert(v);
+                }
+            }
+        }
+    }
+
     /// Merge recursively elements only if the key is present in `left` and `right`.
-    pub fn intersection<K: Eq + Hash, V: super::Merge>(
-        left: &mut HashMap<K, V>,
-        right: &mut HashMap<K, V>,
+    pub fn intersection<K: Eq + Hash, V: super::Merge, S: BuildHasher>(
+        left: &mut HashMap<K, V, S>,
+        right: &mut HashMap<K, V, S>,
     ) {
         use std::collections::hash_map::Entry;
 
-        let map = core::mem::take(right);
-        for (k, mut v) in map {
+        for (k, mut v) in right.drain() {
             if let Entry::Occupied(mut existing) = left.entry(k) {
                 existing.get_mut().merge(&mut v);
             }
         }
     }
+
+    /// Like `recursive`, but elements of `right` whose key doesn't match `pred` are dropped.
+    pub fn merge_if_key<K: Eq + Hash, V: super::Merge, S: BuildHasher, F: Fn(&K) -> bool>(
+        pred: F,
+    ) -> impl Fn(&mut HashMap<K, V, S>, &mut HashMap<K, V, S>) {
+        use std::collections::hash_map::Entry;
+
+        move |left, right| {
+            for (k, mut v) in right.drain().filter(|(k, _)| pred(k)) {
+                match left.entry(k) {
+                    Entry::Occupied(mut existing) => existing.get_mut().merge(&mut v),
+                    Entry::Vacant(empty) => {
+                        empty.insert(v);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Like `recursive`, but the keys are normalized with `norm` first, so keys with the same
+    /// normalized form collide and their values are merged, for example case-insensitive keys.
+    ///
+    /// It rebuilds `left` under the normalized keys. If keys of the same side collide, the order
+    /// in which their values are merged is unspecified.
+    pub fn merge_normalized<K, V, S, F>(
+        norm: F,
+    ) -> impl Fn(&mut HashMap<K, V, S>, &mut HashMap<K, V, S>)
+    where
+        K: Eq + Hash,
+        V: super::Merge,
+        S: BuildHasher,
+        F: Fn(&K) -> K,
+    {
+        use std::collections::hash_map::Entry;
+
+        move |left, right| {
+            let old: Vec<(K, V)> = left.drain().collect();
+            for (k, mut v) in old.into_iter().chain(right.drain()) {
+                match left.entry(norm(&k)) {
+                    Entry::Occupied(mut existing) => existing.get_mut().merge(&mut v),
+                    Entry::Vacant(empty) => {
+                        empty.insert(v);
+                    }
+                }
+            }
+        }
+    }
+
+    /// On conflict, call `keep_left(key, left_value, right_value)` to choose the value, `true`
+    /// keeps the value of `left`. Elements of `right` with new keys are inserted.
+    pub fn resolve<K, V, S, F>(
+        keep_left: F,
+    ) -> impl Fn(&mut HashMap<K, V, S>, &mut HashMap<K, V, S>)
+    where
+        K: Eq + Hash,
+        S: BuildHasher,
+        F: Fn(&K, &V, &V) -> bool,
+    {
+        use std::collections::hash_map::Entry;
+
+        move |left, right| {
+            for (k, v) in right.drain() {
+                match left.entry(k) {
+                    Entry::Occupied(mut existing) => {
+                        if !keep_left(existing.key(), existing.get(), &v) {
+                            existing.insert(v);
+                        }
+                    }
+                    Entry::Vacant(empty) => {
+                        empty.insert(v);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Keep only the elements of `left` whose key is present in `right`.
+    ///
+    /// Unlike `intersection`, values are not merged, `left` values are kept.
+    #[inline]
+    pub fn retain_common<K: Eq + Hash, V, S: BuildHasher>(
+        left: &mut HashMap<K, V, S>,
+        right: &mut HashMap<K, V, S>,
+    ) {
+        left.retain(|k, _| right.contains_key(k));
+    }
+
+    /// Remove the elements of `left` whose key is present in `right`.
+    #[inline]
+    pub fn remove_right<K: Eq + Hash, V, S: BuildHasher>(
+        left: &mut HashMap<K, V, S>,
+        right: &mut HashMap<K, V, S>,
+    ) {
+        left.retain(|k, _| !right.contains_key(k));
+    }
+}
+
+#[cfg(feature = "std")]
+use std::collections::HashSet;
+/// Forms the union of the sets, like `hashset::union`. If `self` is empty, the sets are swapped.
+#[cfg(feature = "std")]
+impl<T: Eq + core::hash::Hash, S: core::hash::BuildHasher> Merge for HashSet<T, S> {
+    #[inline]
+    fn merge(&mut self, right: &mut Self) {
+        if self.is_empty() {
+            core::mem::swap(self, right);
+        } else {
+            self.extend(right.drain());
+        }
+    }
+}
+
+/// Merge strategies for hash sets.
+///
+/// Like the `hashmap` strategies, they work with any hasher `S: BuildHasher`.
+///
+/// These strategies are only available if the `std` feature is enabled.
+#[cfg(feature = "std")]
+pub mod hashset {
+    use super::HashSet;
+    use std::hash::{BuildHasher, Hash};
+
+    /// Add the elements of `right` to `left`. Same as the default `Merge` implementation.
+    #[inline]
+    pub fn union<T: Eq + Hash, S: BuildHasher>(
+        left: &mut HashSet<T, S>,
+        right: &mut HashSet<T, S>,
+    ) {
+        left.extend(right.drain());
+    }
+
+    /// Keep only the elements of `left` that are also in `right`.
+    #[inline]
+    pub fn intersection<T: Eq + Hash, S: BuildHasher>(
+        left: &mut HashSet<T, S>,
+        right: &mut HashSet<T, S>,
+    ) {
+        left.retain(|v| right.contains(v));
+    }
+
+    /// Remove the elements of `right` from `left`.
+    #[inline]
+    pub fn difference<T: Eq + Hash, S: BuildHasher>(
+        left: &mut HashSet<T, S>,
+        right: &mut HashSet<T, S>,
+    ) {
+        left.retain(|v| !right.contains(v));
+    }
+}
+
+#[cfg(feature = "alloc")]
+use alloc::collections::BTreeMap;
+#[cfg(feature = "alloc")]
+impl<K: Ord, V> Merge for BTreeMap<K, V> {
+    #[inline]
+    fn merge(&mut self, right: &mut Self) {
+        if self.is_empty() {
+            core::mem::swap(self, right);
+        }
+    }
+}
+
+/// Merge strategies for ordered maps, the same as the `hashmap` strategies.
+///
+/// These strategies are only available if the `alloc` feature is enabled.
+#[cfg(feature = "alloc")]
+pub mod btreemap {
+    use super::BTreeMap;
+    use alloc::collections::btree_map::Entry;
+
+    /// On conflict, merge elements from `right` to `left`.
+    ///
+    /// In other words, this gives precedence to `left`.
+    #[inline]
+    pub fn merge<K: Ord, V>(left: &mut BTreeMap<K, V>, right: &mut BTreeMap<K, V>) {
+        for (k, v) in core::mem::take(right) {
+            left.entry(k).or_insert(v);
+        }
+    }
+
+    /// On conflict, replace elements of `left` with `right`.
+    ///
+    /// In other words, this gives precedence to `right`.
+    #[inline]
+    pub fn replace<K: Ord, V>(left: &mut BTreeMap<K, V>, right: &mut BTreeMap<K, V>) {
+        left.append(right);
+    }
+
+    /// On conflict, recursively merge the elements.
+    pub fn recursive<K: Ord, V: super::Merge>(
+        left: &mut BTreeMap<K, V>,
+        right: &mut BTreeMap<K, V>,
+    ) {
+        for (k, mut v) in core::mem::take(right) {
+            match left.entry(k) {
+                Entry::Occupied(mut existing) => existing.get_mut().merge(&mut v),
+                Entry::Vacant(empty) => {
+                    empty.insert(v);
+                }
+            }
+        }
+    }
+
+    /// Merge recursively elements only if the key is present in `left` and `right`.
+    pub fn intersection<K: Ord, V: super::Merge>(
+        left: &mut BTreeMap<K, V>,
+        right: &mut BTreeMap<K, V>,
+    ) {
+        for (k, mut v) in core::mem::take(right) {
+            if let Entry::Occupied(mut existing) = left.entry(k) {
+                existing.get_mut().merge(&mut v);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+use alloc::collections::BTreeSet;
+#[cfg(feature = "alloc")]
+impl<T: Ord> Merge for BTreeSet<T> {
+    #[inline]
+    fn merge(&mut self, right: &mut Self) {
+        if self.is_empty() {
+            core::mem::swap(self, right);
+        }
+    }
+}
+
+/// Merge strategies for ordered sets. The elements stay sorted and deduplicated.
+///
+/// The default `Merge` implementation of `BTreeSet` only fills an empty set, use `union` to
+/// accumulate the elements of all layers.
+///
+/// These strategies are only available if the `alloc` feature is enabled.
+#[cfg(feature = "alloc")]
+pub mod btreeset {
+    use super::BTreeSet;
+
+    /// Move all elements of `right` into `left`.
+    #[inline]
+    pub fn union<T: Ord>(left: &mut BTreeSet<T>, right: &mut BTreeSet<T>) {
+        left.append(right);
+    }
+
+    /// Keep only the elements of `left` that are also in `right`.
+    #[inline]
+    pub fn intersection<T: Ord>(left: &mut BTreeSet<T>, right: &mut BTreeSet<T>) {
+        left.retain(|v| right.contains(v));
+    }
+
+    /// Remove the elements of `right` from `left`.
+    #[inline]
+    pub fn difference<T: Ord>(left: &mut BTreeSet<T>, right: &mut BTreeSet<T>) {
+        left.retain(|v| !right.contains(v));
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl Merge for ::bytes::Bytes {
+    #[inline]
+    fn merge(&mut self, right: &mut Self) {
+        if self.is_empty() {
+            core::mem::swap(self, right);
+        }
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl Merge for ::bytes::BytesMut {
+    #[inline]
+    fn merge(&mut self, right: &mut Self) {
+        if self.is_empty() {
+            core::mem::swap(self, right);
+        }
+    }
+}
+
+/// Merge strategies for byte buffers.
+///
+/// `Bytes` is immutable, so it only implements the default `Merge` (swap if `left` is empty).
+/// To append to it, merge into a `BytesMut` and call `freeze` afterwards.
+///
+/// These strategies are only available if the `bytes` feature is enabled.
+#[cfg(feature = "bytes")]
+pub mod bytes {
+    use ::bytes::BytesMut;
+
+    /// Append the contents of right to left.
+    ///
+    /// It's O(1) if `right` was split off the end of `left`, otherwise the contents are copied.
+    #[inline]
+    pub fn append(left: &mut BytesMut, right: &mut BytesMut) {
+        if left.is_empty() {
+            core::mem::swap(left, right);
+        } else {
+            left.unsplit(core::mem::take(right));
+        }
+    }
+}
+
+/// Keeps `left`.
+#[cfg(feature = "chrono")]
+impl<Tz: ::chrono::TimeZone> Merge for ::chrono::DateTime<Tz> {
+    #[inline(always)]
+    fn merge(&mut self, _: &mut Self) {}
+}
+
+/// Merge strategies for dates and times.
+///
+/// `DateTime` values are compared by the instant they represent, regardless of their time zones
+/// or offsets. The selected value keeps its own time zone.
+///
+/// These strategies are only available if the `chrono` feature is enabled.
+#[cfg(feature = "chrono")]
+pub mod chrono {
+    use ::chrono::{DateTime, NaiveDate, TimeZone};
+
+    /// Overwrite `left` with `right` if `right` is a later instant.
+    #[inline]
+    pub fn latest<Tz: TimeZone>(left: &mut DateTime<Tz>, right: &mut DateTime<Tz>) {
+        if *right > *left {
+            core::mem::swap(left, right);
+        }
+    }
+
+    /// Overwrite `left` with `right` if `right` is an earlier instant.
+    #[inline]
+    pub fn earliest<Tz: TimeZone>(left: &mut DateTime<Tz>, right: &mut DateTime<Tz>) {
+        if *right < *left {
+            core::mem::swap(left, right);
+        }
+    }
+
+    /// Overwrite `left` with `right` if `right` is a later date.
+    #[inline]
+    pub fn max(left: &mut NaiveDate, right: &mut NaiveDate) {
+        if *right > *left {
+            core::mem::swap(left, right);
+        }
+    }
+
+    /// Overwrite `left` with `right` if `right` is an earlier date.
+    #[inline]
+    pub fn min(left: &mut NaiveDate, right: &mut NaiveDate) {
+        if *right < *left {
+            core::mem::swap(left, right);
+        }
+    }
+}
+
+/// A registry of named merge strategies, for choosing a strategy at runtime.
+///
+/// The registry is typed: all strategies of a `StrategyRegistry<T>` merge values of type `T`.
+///
+/// ```
+/// use merge2::registry::StrategyRegistry;
+///
+/// let mut registry = StrategyRegistry::vec();
+/// registry.register("first", |left: &mut Vec<u8>, _: &mut Vec<u8>| left.truncate(1));
+///
+/// let mut left = vec![1, 2];
+/// assert!(registry.merge("append", &mut left, &mut vec![3]));
+/// assert_eq!(vec![1, 2, 3], left);
+/// assert!(registry.merge("first", &mut left, &mut vec![]));
+/// assert_eq!(vec![1], left);
+/// assert!(!registry.merge("unknown", &mut left, &mut vec![]));
+/// ```
+///
+/// This module is only available if the `alloc` feature is enabled.
+#[cfg(feature = "alloc")]
+pub mod registry {
+    use super::Merge;
+    use alloc::boxed::Box;
+    use alloc::collections::BTreeMap;
+    use alloc::vec::Vec;
+
+    type DynStrategy<T> = dyn Fn(&mut T, &mut T);
+    type Strategy<T> = Box<DynStrategy<T>>;
+
+    /// Merge strategies for the type `T`, looked up by name.
+    pub struct StrategyRegistry<T> {
+        strategies: BTreeMap<&'static str, Strategy<T>>,
+    }
+
+    impl<T> Default for StrategyRegistry<T> {
+        #[inline]
+        fn default() -> Self {
+            Self {
+                strategies: BTreeMap::new(),
+            }
+        }
+    }
+
+    impl<T> core::fmt::Debug for StrategyRegistry<T> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            f.debug_set().entries(self.strategies.keys()).finish()
+        }
+    }
+
+    impl<T> StrategyRegistry<T> {
+        /// Create an empty registry.
+        #[inline]
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Register a strategy under `name`, replacing the strategy registered under the same name.
+        pub fn register<F>(&mut self, name: &'static str, strategy: F) -> &mut Self
+        where
+            F: Fn(&mut T, &mut T) + 'static,
+        {
+            self.strategies.insert(name, Box::new(strategy));
+            self
+        }
+
+        /// Get the strategy registered under `name`.
+        #[inline]
+        pub fn get(&self, name: &str) -> Option<&DynStrategy<T>> {
+            self.strategies.get(name).map(Box::as_ref)
+        }
+
+        /// Merge `right` into `left` with the strategy registered under `name`.
+        /// Returns `false` and leaves both values untouched if there is no such strategy.
+        pub fn merge(&self, name: &str, left: &mut T, right: &mut T) -> bool {
+            match self.get(name) {
+                Some(strategy) => {
+                    strategy(left, right);
+                    true
+                }
+                None => false,
+            }
+        }
+
+        /// The names of the registered strategies, in sorted order.
+        #[inline]
+        pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+            self.strategies.keys().copied()
+        }
+    }
+
+    impl<T: Merge + 'static> StrategyRegistry<T> {
+        /// Create a registry with the default `Merge` implementation registered as `"merge"`, and
+        /// the strategies of the `any` module that don't require extra bounds: `"swap"`.
+        pub fn with_merge() -> Self {
+            let mut registry = Self::new();
+            registry
+                .register("merge", T::merge)
+                .register("swap", super::any::swap);
+            registry
+        }
+    }
+
+    impl<T: 'static> StrategyRegistry<Vec<T>> {
+        /// Create a registry with the `vec` strategies that don't require extra bounds:
+        /// `"merge"`, `"swap"`, `"overwrite_if_empty"`, `"append"`, `"prepend"`, `"clamp_len"` and
+        /// `"interleave"`.
+        pub fn vec() -> Self {
+            let mut registry = Self::with_merge();
+            registry
+                .register("overwrite_if_empty", super::vec::overwrite_if_empty)
+                .register("append", super::vec::append)
+                .register("prepend", super::vec::prepend)
+                .register("clamp_len", super::vec::clamp_len)
+                .register("interleave", super::vec::interleave);
+            registry
+        }
+    }
+
+    impl<T: 'static> StrategyRegistry<Option<T>>
+    where
+        Option<T>: Merge,
+    {
+        /// Create a registry with the `option` strategies that don't require extra bounds:
+        /// `"merge"`, `"swap"`, `"overwrite_some"` and `"prefer_exclusive"`.
+        pub fn option() -> Self {
+            let mut registry = Self::with_merge();
+            registry
+                .register("overwrite_some", super::option::overwrite_some)
+                .register("prefer_exclusive", super::option::prefer_exclusive);
+            registry
+        }
+    }
 }
@@ -1,5 +1,4 @@
 #[test]
-#[ignore]
 fn test_compile_fail() {
     let t = trybuild::TestCases::new();
     t.compile_fail("tests/compile/*.rs");
@@ -0,0 +1,9 @@
+use merge2::Merge;
+
+#[derive(Merge)]
+#[merge(clone_ref)]
+struct S {
+    field1: Option<u8>,
+}
+
+fn main() {}
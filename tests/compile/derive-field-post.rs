@@ -0,0 +1,11 @@
+use merge2::Merge;
+
+#[derive(Merge)]
+struct S {
+    #[merge(post = post)]
+    field1: Option<u8>,
+}
+
+fn post(_: &mut S, _: &mut S) {}
+
+fn main() {}
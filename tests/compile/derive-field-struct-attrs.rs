@@ -0,0 +1,18 @@
+use merge2::Merge;
+
+#[derive(Merge)]
+struct Transparent(#[merge(transparent)] Option<u8>);
+
+#[derive(Merge)]
+struct Bound<T> {
+    #[merge(bound = "T: Merge")]
+    field1: Option<T>,
+}
+
+#[derive(Merge)]
+struct CloneRef {
+    #[merge(clone_ref)]
+    field1: Option<u8>,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use merge2::Merge;
+
+#[derive(Merge)]
+struct S {
+    field1: Option<u8>,
+    #[merge(strategy = ::merge2::any::swap)]
+    field2: *mut u8,
+    field3: fn(u8) -> u8,
+}
+
+fn main() {}
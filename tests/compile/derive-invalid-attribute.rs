@@ -2,7 +2,7 @@ use merge2::Merge;
 
 #[derive(Merge)]
 struct S {
-    #[merge(ignore)]
+    #[merge(omit)]
     field1: Option<u8>,
 }
 
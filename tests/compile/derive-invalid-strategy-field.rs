@@ -0,0 +1,19 @@
+use merge2::Merge;
+
+struct Strategies;
+
+impl Strategies {
+    fn overwrite(&self, left: &mut u8, right: &mut u8) {
+        *left = *right
+    }
+}
+
+const STRATEGIES: Strategies = Strategies;
+
+#[derive(Merge)]
+struct S {
+    #[merge(strategy = STRATEGIES.overwrite)]
+    field1: u8,
+}
+
+fn main() {}
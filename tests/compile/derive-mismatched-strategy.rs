@@ -0,0 +1,13 @@
+use merge2::Merge;
+
+#[derive(Merge)]
+struct S {
+    #[merge(strategy = my_custom_merge_strategy)]
+    field1: u16,
+}
+
+fn my_custom_merge_strategy(left: &mut u8, right: &mut u8) {
+    *left += *right
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use merge2::Merge;
+
+#[derive(Merge)]
+struct Default {
+    #[merge(strategy = ::merge2::ord::max)]
+    #[merge(default = 0)]
+    field1: u8,
+}
+
+#[derive(Merge)]
+struct InnerStrategy {
+    #[merge(inner_strategy = ::merge2::ord::max, strategy = ::merge2::option::overwrite_some)]
+    field1: Option<u8>,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+#![deny(deprecated)]
+
+use merge2::Merge;
+
+#[derive(Merge)]
+struct S {
+    #[merge(skip)]
+    field: Option<u8>,
+}
+
+fn main() {}
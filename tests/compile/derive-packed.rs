@@ -0,0 +1,10 @@
+use merge2::Merge;
+
+#[derive(Merge)]
+#[repr(C, packed)]
+struct S {
+    field1: u8,
+    field2: u32,
+}
+
+fn main() {}
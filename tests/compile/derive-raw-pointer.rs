@@ -0,0 +1,11 @@
+use merge2::Merge;
+
+#[derive(Merge)]
+struct S {
+    field1: Option<u8>,
+    field2: *const u8,
+    #[merge(skip)]
+    field3: fn(),
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use merge2::Merge;
+
+#[derive(Merge)]
+struct S {
+    #[merge(inner_strategy = ::merge2::ord::max, skip)]
+    field1: Option<u8>,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use merge2::Merge;
+
+#[derive(Merge)]
+struct S {
+    #[merge(skip, reverse)]
+    field1: Option<u8>,
+    field2: Option<u8>,
+}
+
+fn main() {}
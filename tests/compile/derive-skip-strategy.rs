@@ -0,0 +1,10 @@
+use merge2::Merge;
+
+#[derive(Merge)]
+struct S {
+    #[merge(skip)]
+    #[merge(strategy = ::merge2::any::overwrite)]
+    field1: u8,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use merge2::Merge;
+
+#[derive(Merge)]
+struct S {
+    #[merge(strategy = ::merge2::any::overwrite)]
+    #[merge(skip)]
+    field1: u8,
+}
+
+fn main() {}
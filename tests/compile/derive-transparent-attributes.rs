@@ -0,0 +1,18 @@
+use merge2::Merge;
+
+#[derive(Merge)]
+#[merge(transparent)]
+struct Skip(#[merge(skip)] Option<u8>);
+
+#[derive(Merge)]
+#[merge(transparent)]
+struct Strategy {
+    #[merge(strategy = ::merge2::ord::max)]
+    field1: u8,
+}
+
+#[derive(Merge)]
+#[merge(transparent, strategy = ::merge2::ord::max)]
+struct ContainerStrategy(u8);
+
+fn main() {}
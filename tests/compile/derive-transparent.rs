@@ -0,0 +1,7 @@
+use merge2::Merge;
+
+#[derive(Merge)]
+#[merge(transparent)]
+struct S(Option<u8>, Option<u8>);
+
+fn main() {}
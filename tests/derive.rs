@@ -129,6 +129,243 @@ fn test_two_option_fields() {
     test(S::new(None, None), S::new(None, None), S::new(None, None));
 }
 
+#[test]
+fn test_control_flow() {
+    use std::ops::ControlFlow;
+
+    #[derive(Debug, Merge, PartialEq)]
+    struct S<B, C> {
+        field1: Option<u8>,
+        state: ControlFlow<B, C>,
+    }
+
+    test(
+        S {
+            field1: Some(1),
+            state: ControlFlow::<u8, ()>::Continue(()),
+        },
+        S {
+            field1: None,
+            state: ControlFlow::Continue(()),
+        },
+        S {
+            field1: Some(1),
+            state: ControlFlow::Break(2),
+        },
+    );
+}
+
+#[test]
+fn test_infallible() {
+    use std::convert::Infallible;
+    use std::marker::PhantomData;
+
+    #[derive(Debug, Merge, PartialEq)]
+    struct S<E> {
+        field1: Option<u8>,
+        errors: [E; 0],
+        #[merge(skip)]
+        marker: PhantomData<E>,
+    }
+
+    let s = |field1| S::<Infallible> {
+        field1,
+        errors: [],
+        marker: PhantomData,
+    };
+
+    test(s(Some(1)), s(None), s(Some(1)));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_lifetimes() {
+    #[derive(Debug, Merge, PartialEq)]
+    struct S<'a> {
+        name: Option<&'a str>,
+        #[merge(strategy = ::merge2::option::recursive)]
+        alias: Option<&'a str>,
+        path: &'a str,
+    }
+
+    let (empty, a, b) = (String::new(), "a".to_owned(), "b".to_owned());
+
+    test(
+        S {
+            name: Some(&a),
+            alias: Some(&b),
+            path: &b,
+        },
+        S {
+            name: Some(&a),
+            alias: Some(&empty),
+            path: &empty,
+        },
+        S {
+            name: Some(&b),
+            alias: Some(&b),
+            path: &b,
+        },
+    );
+    test(
+        S {
+            name: Some(&b),
+            alias: Some(&a),
+            path: &a,
+        },
+        S {
+            name: None,
+            alias: Some(&a),
+            path: &a,
+        },
+        S {
+            name: Some(&b),
+            alias: Some(&b),
+            path: &b,
+        },
+    );
+}
+
+#[test]
+fn test_cfg_fields() {
+    #[derive(Debug, Merge, PartialEq)]
+    struct S {
+        field1: Option<usize>,
+        #[cfg(test)]
+        field2: Option<usize>,
+        #[cfg(any())]
+        field3: Option<usize>,
+        #[cfg(feature = "std")]
+        #[merge(strategy = ::merge2::vec::append)]
+        field4: Vec<usize>,
+    }
+
+    let s = |v: Option<usize>| S {
+        field1: v,
+        field2: v,
+        #[cfg(feature = "std")]
+        field4: v.into_iter().collect(),
+    };
+
+    test(s(Some(1)), s(None), s(Some(1)));
+    #[cfg(feature = "std")]
+    test(
+        S {
+            field1: Some(1),
+            field2: Some(1),
+            field4: vec![1, 2],
+        },
+        s(Some(1)),
+        s(Some(2)),
+    );
+}
+
+#[test]
+fn test_ignore() {
+    #[derive(Debug, Merge, PartialEq)]
+    struct S(Option<usize>, #[merge(ignore)] Option<usize>);
+
+    test(S(Some(1), None), S(None, None), S(Some(1), Some(2)));
+    test(
+        S(Some(1), Some(3)),
+        S(Some(1), Some(3)),
+        S(Some(2), Some(4)),
+    );
+}
+
+#[test]
+fn test_multiple_attributes() {
+    #[derive(Debug, Merge, PartialEq)]
+    struct S(
+        #[merge(strategy = ::merge2::any::overwrite)]
+        #[merge(strategy = ::merge2::ord::max)]
+        u8,
+        #[merge(strategy = ::merge2::ord::max)]
+        #[merge(strategy = ::merge2::any::overwrite)]
+        u8,
+    );
+
+    test(S(3, 2), S(3, 3), S(2, 2));
+    test(S(2, 2), S(1, 1), S(2, 2));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_reverse() {
+    #[derive(Debug, Merge, PartialEq)]
+    struct S(
+        #[merge(strategy = ::merge2::vec::prepend)] Vec<u8>,
+        #[merge(strategy = ::merge2::vec::append, reverse)] Vec<u8>,
+        #[merge(reverse)] Option<u8>,
+    );
+
+    let s = |v: Vec<u8>, o| S(v.clone(), v, o);
+
+    test(
+        s(vec![3, 4, 1, 2], Some(2)),
+        s(vec![1, 2], Some(1)),
+        s(vec![3, 4], Some(2)),
+    );
+    test(
+        s(vec![1, 2], Some(1)),
+        s(vec![1, 2], Some(1)),
+        s(vec![], None),
+    );
+    test(
+        s(vec![3, 4], Some(2)),
+        s(vec![], None),
+        s(vec![3, 4], Some(2)),
+    );
+
+    let mut left = s(vec![1], None);
+    let mut right = s(vec![2], Some(2));
+    left.merge(&mut right);
+    assert_eq!(s(vec![], None), right);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_inner_strategy() {
+    #[derive(Debug, Merge, PartialEq)]
+    struct S(
+        #[merge(inner_strategy = ::merge2::vec::append)] Option<Vec<u8>>,
+        #[merge(inner_strategy = |l: &mut u8, r: &mut u8| *l += *r)] Option<u8>,
+    );
+
+    test(
+        S(Some(vec![1, 2]), Some(3)),
+        S(Some(vec![1]), Some(1)),
+        S(Some(vec![2]), Some(2)),
+    );
+    test(
+        S(Some(vec![1]), Some(1)),
+        S(Some(vec![1]), Some(1)),
+        S(None, None),
+    );
+    test(
+        S(Some(vec![2]), Some(2)),
+        S(None, None),
+        S(Some(vec![2]), Some(2)),
+    );
+    test(S(None, None), S(None, None), S(None, None));
+}
+
+#[test]
+fn test_default_expr() {
+    const UNSET: &str = "unset";
+
+    #[derive(Debug, Merge, PartialEq)]
+    struct S(
+        #[merge(default = -1)] i32,
+        #[merge(default = UNSET)] &'static str,
+        #[merge(default = i32::MAX)] i32,
+    );
+
+    test(S(2, "b", 2), S(-1, UNSET, i32::MAX), S(2, "b", 2));
+    test(S(1, "a", 1), S(1, "a", 1), S(2, "b", 2));
+    test(S(0, "", 0), S(0, "", 0), S(2, "b", 2));
+}
+
 #[test]
 fn test_skip_valid() {
     #[derive(Debug, Merge, PartialEq)]
@@ -551,6 +788,33 @@ fn test_default_strategy() {
     );
 }
 
+#[test]
+#[cfg(all(feature = "num", feature = "std"))]
+fn test_post_default_strategy() {
+    #[derive(Debug, Merge, PartialEq)]
+    struct N(#[merge(strategy = ::merge2::num::saturating_add)] u8);
+
+    #[derive(Debug, Merge, PartialEq)]
+    #[merge(post = total)]
+    struct S(
+        Option<usize>,
+        Option<usize>,
+        #[merge(strategy = ::merge2::num::saturating_add)] u8,
+        #[merge(strategy = Merge::merge)] N,
+        #[merge(skip)] u8,
+    );
+
+    fn total(left: &mut S, _: &mut S) {
+        left.4 = left.2.saturating_add(left.3 .0);
+    }
+
+    test(
+        S(Some(1), Some(2), 3, N(3), 6),
+        S(Some(1), None, 1, N(1), 0),
+        S(Some(2), Some(2), 2, N(2), 0),
+    );
+}
+
 #[test]
 fn test_generics() {
     #[derive(Debug, Merge, PartialEq)]
@@ -591,4 +855,907 @@ fn test_generics() {
         a: Option<A>,
         b: Option<B>,
     }
+
+    test(
+        TupleWithGenerics(Some(1), Some("b")),
+        TupleWithGenerics(Some(1), None),
+        TupleWithGenerics(Some(2), Some("b")),
+    );
+    test(
+        TupleWithWhere(Some(1), Some("b")),
+        TupleWithWhere(None, Some("b")),
+        TupleWithWhere(Some(1), Some("c")),
+    );
+    test(
+        TupleWithBoth(Some(1), Some("b")),
+        TupleWithBoth(None, None),
+        TupleWithBoth(Some(1), Some("b")),
+    );
+    test(
+        StructWithGenerics {
+            a: Some(1),
+            b: Some("b"),
+        },
+        StructWithGenerics {
+            a: Some(1),
+            b: None,
+        },
+        StructWithGenerics {
+            a: Some(2),
+            b: Some("b"),
+        },
+    );
+    test(
+        StructWithWhere {
+            a: Some(1),
+            b: Some("b"),
+        },
+        StructWithWhere {
+            a: None,
+            b: Some("b"),
+        },
+        StructWithWhere {
+            a: Some(1),
+            b: Some("c"),
+        },
+    );
+    test(
+        StructWithBoth {
+            a: Some(1),
+            b: Some("b"),
+        },
+        StructWithBoth { a: None, b: None },
+        StructWithBoth {
+            a: Some(1),
+            b: Some("b"),
+        },
+    );
+}
+
+#[test]
+fn test_post() {
+    #[derive(Debug, Merge, PartialEq)]
+    #[merge(post = total)]
+    struct S {
+        field1: Option<usize>,
+        field2: Option<usize>,
+        #[merge(skip)]
+        total: usize,
+    }
+
+    impl S {
+        pub fn new(field1: Option<usize>, field2: Option<usize>, total: usize) -> S {
+            S {
+                field1,
+                field2,
+                total,
+            }
+        }
+    }
+
+    fn total(left: &mut S, _: &mut S) {
+        left.total = left.field1.unwrap_or_default() + left.field2.unwrap_or_default();
+    }
+
+    test(
+        S::new(Some(1), Some(2), 3),
+        S::new(Some(1), None, 0),
+        S::new(None, Some(2), 0),
+    );
+    test(
+        S::new(Some(1), Some(2), 3),
+        S::new(Some(1), Some(2), 0),
+        S::new(Some(3), Some(4), 0),
+    );
+    test(
+        S::new(None, Some(4), 4),
+        S::new(None, None, 1),
+        S::new(None, Some(4), 2),
+    );
+    test(
+        S::new(None, None, 0),
+        S::new(None, None, 5),
+        S::new(None, None, 5),
+    );
+}
+
+#[test]
+fn test_doc() {
+    #[derive(Debug, Merge, PartialEq)]
+    #[merge(doc = "Keeps the first `field1` that is set.")]
+    #[merge(doc = "")]
+    #[merge(doc = "`field2` is the largest value.")]
+    struct S {
+        field1: Option<u8>,
+        #[merge(strategy = ::merge2::ord::max)]
+        field2: u8,
+    }
+
+    test(
+        S {
+            field1: Some(1),
+            field2: 2,
+        },
+        S {
+            field1: Some(1),
+            field2: 1,
+        },
+        S {
+            field1: Some(2),
+            field2: 2,
+        },
+    );
+}
+
+#[test]
+fn test_respect_serde_skip() {
+    use serde::Serialize;
+
+    #[derive(Debug, Merge, PartialEq, Serialize)]
+    #[merge(respect_serde_skip)]
+    struct S {
+        field1: Option<usize>,
+        #[serde(skip)]
+        field2: Option<usize>,
+        #[serde(default, skip)]
+        #[merge(strategy = ::merge2::option::overwrite_some)]
+        field3: Option<usize>,
+        #[serde(skip_serializing)]
+        field4: Option<usize>,
+    }
+
+    let s = |v| S {
+        field1: v,
+        field2: v,
+        field3: v,
+        field4: v,
+    };
+
+    test(
+        S {
+            field1: Some(1),
+            field2: None,
+            field3: Some(1),
+            field4: Some(1),
+        },
+        s(None),
+        s(Some(1)),
+    );
+}
+
+#[test]
+fn test_respect_serde_skip_not_merge() {
+    use serde::Serialize;
+
+    #[derive(Debug, PartialEq)]
+    struct NotMerge(u8);
+
+    #[derive(Merge, Serialize)]
+    #[merge(respect_serde_skip)]
+    struct S {
+        field1: Option<usize>,
+        #[serde(skip)]
+        callback: fn() -> u8,
+        #[serde(skip)]
+        not_merge: NotMerge,
+    }
+
+    let mut left = S {
+        field1: None,
+        callback: || 1,
+        not_merge: NotMerge(1),
+    };
+    left.merge(&mut S {
+        field1: Some(2),
+        callback: || 2,
+        not_merge: NotMerge(2),
+    });
+    assert_eq!(Some(2), left.field1);
+    assert_eq!(1, (left.callback)());
+    assert_eq!(NotMerge(1), left.not_merge);
+}
+
+#[test]
+fn test_transparent() {
+    #[derive(Debug, PartialEq)]
+    struct Sum(u8);
+
+    impl Merge for Sum {
+        fn merge(&mut self, other: &mut Self) {
+            self.0 += other.0;
+        }
+    }
+
+    #[derive(Debug, Merge, PartialEq)]
+    #[merge(transparent)]
+    struct W(Option<u8>);
+
+    #[derive(Debug, Merge, PartialEq)]
+    #[merge(transparent)]
+    struct S {
+        field1: Sum,
+    }
+
+    #[derive(Debug, Merge, PartialEq)]
+    #[merge(transparent, option = right)]
+    struct R(Option<u8>);
+
+    test(W(Some(1)), W(Some(1)), W(Some(2)));
+    test(W(Some(2)), W(None), W(Some(2)));
+    test(
+        S { field1: Sum(3) },
+        S { field1: Sum(1) },
+        S { field1: Sum(2) },
+    );
+    test(R(Some(1)), R(Some(1)), R(Some(2)));
+}
+
+#[test]
+fn test_skip_default_other() {
+    #[derive(Debug, Default, Merge, PartialEq)]
+    #[merge(skip_default_other)]
+    struct S {
+        field1: Option<usize>,
+        #[merge(strategy = ::merge2::any::overwrite)]
+        field2: usize,
+    }
+
+    impl S {
+        pub fn new(field1: Option<usize>, field2: usize) -> S {
+            S { field1, field2 }
+        }
+    }
+
+    // `any::overwrite` would reset field2, but a default right side isn't merged at all
+    test(S::new(Some(1), 2), S::new(Some(1), 2), S::new(None, 0));
+    test(S::new(Some(1), 0), S::new(Some(1), 2), S::new(Some(3), 0));
+    test(S::new(Some(3), 4), S::new(None, 2), S::new(Some(3), 4));
+    test(S::new(None, 0), S::new(None, 0), S::new(None, 0));
+}
+
+#[test]
+fn test_skip_default_other_generic() {
+    #[derive(Debug, Default, Merge, PartialEq)]
+    #[merge(skip_default_other)]
+    struct S<T>(Option<T>);
+
+    test(S(Some(1)), S(Some(1)), S(None));
+    test(S(Some(2)), S(None), S(Some(2)));
+}
+
+#[test]
+fn test_skip_default_other_post() {
+    #[derive(Debug, Default, Merge, PartialEq)]
+    #[merge(skip_default_other, post = count)]
+    struct S {
+        field1: Option<usize>,
+        #[merge(skip)]
+        merges: usize,
+    }
+
+    fn count(left: &mut S, _: &mut S) {
+        left.merges += 1;
+    }
+
+    let s = |field1, merges| S { field1, merges };
+
+    // The post function isn't called if the merge returns early
+    test(s(Some(1), 0), s(Some(1), 0), s(None, 0));
+    test(s(Some(1), 1), s(None, 0), s(Some(1), 0));
+    test(s(Some(1), 1), s(Some(1), 0), s(Some(2), 0));
+}
+
+#[test]
+fn test_skip_default_other_clone_ref() {
+    #[derive(Clone, Debug, Default, Merge, PartialEq)]
+    #[merge(skip_default_other, clone_ref)]
+    struct S<T>(Option<T>);
+
+    let mut s = S(None);
+    s.merge_ref(&S(Some(1)));
+    assert_eq!(S(Some(1)), s);
+    s.merge_ref(&S(None));
+    assert_eq!(S(Some(1)), s);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_clone_ref() {
+    #[derive(Clone, Debug, Merge, PartialEq)]
+    #[merge(clone_ref)]
+    struct S {
+        field1: Option<usize>,
+        #[merge(strategy = ::merge2::vec::append)]
+        field2: Vec<usize>,
+    }
+
+    let shared = S {
+        field1: Some(2),
+        field2: vec![2],
+    };
+    let layers = [&shared, &shared];
+
+    let mut s = S {
+        field1: None,
+        field2: vec![1],
+    };
+    for layer in layers {
+        s.merge_ref(layer);
+    }
+    assert_eq!(
+        S {
+            field1: Some(2),
+            field2: vec![1, 2, 2],
+        },
+        s
+    );
+    assert_eq!(vec![2], shared.field2);
+}
+
+#[test]
+fn test_clone_ref_generic() {
+    #[derive(Clone, Debug, Merge, PartialEq)]
+    #[merge(clone_ref)]
+    struct S<T> {
+        field1: Option<T>,
+    }
+
+    let shared = S { field1: Some(2) };
+    let mut s = S { field1: None };
+    s.merge_ref(&shared);
+    assert_eq!(shared, s);
+}
+
+#[test]
+fn test_merge_iter() {
+    use merge2::MergeExt;
+
+    #[derive(Debug, Merge, PartialEq)]
+    struct S {
+        field1: Option<usize>,
+        field2: Option<usize>,
+        field3: Option<usize>,
+    }
+
+    impl S {
+        pub fn new(field1: Option<usize>, field2: Option<usize>, field3: Option<usize>) -> S {
+            S {
+                field1,
+                field2,
+                field3,
+            }
+        }
+    }
+
+    let mut base = S::new(Some(0), None, None);
+    let mut layers = [
+        S::new(Some(1), None, None),
+        S::new(Some(2), Some(2), None),
+        S::new(Some(3), Some(3), Some(3)),
+    ];
+    base.merge_iter(&mut layers);
+    assert_eq!(S::new(Some(0), Some(2), Some(3)), base);
+
+    let mut base = S::new(None, None, None);
+    base.merge_iter(&mut []);
+    assert_eq!(S::new(None, None, None), base);
+}
+
+#[test]
+fn test_generics_bounds() {
+    #[derive(Debug, PartialEq)]
+    struct NotMerge(u8);
+
+    #[derive(Debug, Merge, PartialEq)]
+    struct W<T> {
+        #[merge(skip)]
+        raw: T,
+        val: Option<u32>,
+    }
+
+    #[derive(Debug, Merge, PartialEq)]
+    struct V<T> {
+        #[merge(strategy = ::merge2::any::swap)]
+        raw: T,
+        val: Option<u32>,
+    }
+
+    #[derive(Debug, Merge, PartialEq)]
+    struct M<T> {
+        inner: T,
+    }
+
+    test(
+        W {
+            raw: NotMerge(1),
+            val: Some(2),
+        },
+        W {
+            raw: NotMerge(1),
+            val: None,
+        },
+        W {
+            raw: NotMerge(3),
+            val: Some(2),
+        },
+    );
+    test(
+        V {
+            raw: NotMerge(3),
+            val: Some(1),
+        },
+        V {
+            raw: NotMerge(1),
+            val: Some(1),
+        },
+        V {
+            raw: NotMerge(3),
+            val: Some(2),
+        },
+    );
+    test(
+        M { inner: Some(1u8) },
+        M { inner: None },
+        M { inner: Some(1u8) },
+    );
+}
+
+#[test]
+fn test_bound() {
+    #[derive(Debug, Merge, PartialEq)]
+    #[merge(bound = "T: PartialOrd, Option<T>: ::merge2::Merge")]
+    struct S<T> {
+        #[merge(strategy = ::merge2::ord::max)]
+        field1: T,
+        field2: Option<T>,
+    }
+
+    impl<T> S<T> {
+        pub fn new(field1: T, field2: Option<T>) -> S<T> {
+            S { field1, field2 }
+        }
+    }
+
+    test(S::new(2, Some(1)), S::new(1, Some(1)), S::new(2, Some(2)));
+    test(S::new(2, Some(2)), S::new(2, None), S::new(1, Some(2)));
+}
+
+#[test]
+fn test_bound_wrapper() {
+    #[derive(Debug, PartialEq)]
+    struct Wrapper<T>(T);
+
+    impl<T: Merge> Merge for Wrapper<T> {
+        fn merge(&mut self, other: &mut Self) {
+            self.0.merge(&mut other.0);
+        }
+    }
+
+    // Without `bound`, the generated bound would be `Wrapper<T>: Merge`
+    #[derive(Debug, Merge, PartialEq)]
+    #[merge(bound = "T: ::merge2::Merge")]
+    struct S<T> {
+        field: Wrapper<T>,
+    }
+
+    fn merge<T: Merge>(left: &mut S<T>, right: &mut S<T>) {
+        left.merge(right);
+    }
+
+    let mut left = S {
+        field: Wrapper(None),
+    };
+    merge(
+        &mut left,
+        &mut S {
+            field: Wrapper(Some(1)),
+        },
+    );
+    assert_eq!(
+        S {
+            field: Wrapper(Some(1))
+        },
+        left
+    );
+}
+
+#[test]
+fn test_raw_identifiers() {
+    #[derive(Debug, Merge, PartialEq)]
+    struct S {
+        r#type: Option<u32>,
+        #[merge(strategy = ::merge2::any::overwrite_default)]
+        r#match: u32,
+    }
+
+    impl S {
+        pub fn new(r#type: Option<u32>, r#match: u32) -> S {
+            S { r#type, r#match }
+        }
+    }
+
+    test(S::new(Some(1), 1), S::new(Some(1), 1), S::new(Some(2), 2));
+    test(S::new(Some(2), 2), S::new(None, 0), S::new(Some(2), 2));
+}
+
+#[test]
+fn test_post_flag_counter() {
+    use merge2::MergeExt;
+
+    #[derive(Debug, Merge, PartialEq)]
+    #[merge(post = count)]
+    struct S {
+        #[merge(strategy = ::merge2::bool::overwrite_false)]
+        flag: bool,
+        #[merge(skip)]
+        counter: u32,
+    }
+
+    impl S {
+        pub fn new(flag: bool, counter: u32) -> S {
+            S { flag, counter }
+        }
+    }
+
+    fn count(left: &mut S, right: &mut S) {
+        if right.flag {
+            left.counter += 1;
+        }
+    }
+
+    test(S::new(true, 1), S::new(false, 0), S::new(true, 0));
+    test(S::new(true, 0), S::new(true, 0), S::new(false, 0));
+    test(S::new(true, 2), S::new(true, 1), S::new(true, 5));
+
+    let mut base = S::new(false, 0);
+    let mut layers = [
+        S::new(true, 0),
+        S::new(false, 0),
+        S::new(true, 0),
+        S::new(true, 0),
+    ];
+    base.merge_iter(&mut layers);
+    assert_eq!(S::new(true, 3), base);
+}
+
+#[test]
+fn test_post_count_enabled() {
+    #[derive(Debug, Merge, PartialEq)]
+    #[merge(post = count_enabled)]
+    struct S {
+        #[merge(skip)]
+        enabled: bool,
+        #[merge(skip)]
+        enabled_count: u32,
+    }
+
+    impl S {
+        pub fn new(enabled: bool, enabled_count: u32) -> S {
+            S {
+                enabled,
+                enabled_count,
+            }
+        }
+    }
+
+    fn count_enabled(left: &mut S, right: &mut S) {
+        if right.enabled {
+            left.enabled_count += 1;
+        }
+    }
+
+    test(S::new(false, 1), S::new(false, 0), S::new(true, 0));
+    test(S::new(true, 0), S::new(true, 0), S::new(false, 0));
+    test(S::new(true, 3), S::new(true, 2), S::new(true, 5));
+}
+
+#[test]
+fn test_const_generics() {
+    #[derive(Debug, Merge, PartialEq)]
+    struct Buf<const N: usize> {
+        data: Option<[u8; N]>,
+        #[merge(strategy = or)]
+        flags: [u8; N],
+    }
+
+    fn or<const N: usize>(left: &mut [u8; N], right: &mut [u8; N]) {
+        for (l, r) in left.iter_mut().zip(right.iter()) {
+            *l |= *r;
+        }
+    }
+
+    test(
+        Buf {
+            data: Some([1, 2]),
+            flags: [0b11, 0b10],
+        },
+        Buf {
+            data: None,
+            flags: [0b01, 0b00],
+        },
+        Buf {
+            data: Some([1, 2]),
+            flags: [0b10, 0b10],
+        },
+    );
+    test(
+        Buf {
+            data: Some([1, 2, 3]),
+            flags: [0, 0, 1],
+        },
+        Buf {
+            data: Some([1, 2, 3]),
+            flags: [0, 0, 1],
+        },
+        Buf {
+            data: Some([3, 2, 1]),
+            flags: [0, 0, 0],
+        },
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_weak() {
+    use std::rc::{Rc, Weak};
+
+    #[derive(Debug, PartialEq)]
+    struct Inner(u8);
+
+    #[derive(Debug, Merge)]
+    struct S {
+        parent: Weak<Inner>,
+        shared: std::sync::Weak<Inner>,
+        value: Option<u8>,
+    }
+
+    let parent1 = Rc::new(Inner(1));
+    let parent2 = Rc::new(Inner(2));
+    let shared = std::sync::Arc::new(Inner(3));
+
+    let mut left = S {
+        parent: Rc::downgrade(&parent1),
+        shared: std::sync::Weak::new(),
+        value: None,
+    };
+    left.merge(&mut S {
+        parent: Rc::downgrade(&parent2),
+        shared: std::sync::Arc::downgrade(&shared),
+        value: Some(1),
+    });
+    assert!(left.parent.ptr_eq(&Rc::downgrade(&parent1)));
+    assert!(left.shared.upgrade().is_none());
+    assert_eq!(Some(1), left.value);
+}
+
+#[test]
+fn test_option_right() {
+    #[derive(Debug, Merge, PartialEq)]
+    #[merge(option = right)]
+    struct S {
+        field1: Option<usize>,
+        field2: core::option::Option<usize>,
+        #[merge(strategy = ::merge2::option::recursive)]
+        field3: Option<Option<usize>>,
+        field4: usize,
+    }
+
+    impl S {
+        pub fn new(
+            field1: Option<usize>,
+            field2: Option<usize>,
+            field3: Option<Option<usize>>,
+        ) -> S {
+            S {
+                field1,
+                field2,
+                field3,
+                field4: 0,
+            }
+        }
+    }
+
+    test(
+        S::new(Some(2), Some(2), Some(Some(1))),
+        S::new(Some(1), Some(1), Some(Some(1))),
+        S::new(Some(2), Some(2), Some(Some(2))),
+    );
+    test(
+        S::new(Some(1), Some(1), Some(Some(2))),
+        S::new(Some(1), Some(1), Some(None)),
+        S::new(None, None, Some(Some(2))),
+    );
+    test(
+        S::new(Some(2), None, Some(None)),
+        S::new(None, None, None),
+        S::new(Some(2), None, Some(None)),
+    );
+}
+
+#[test]
+fn test_strategy_associated_fn() {
+    trait Combine {
+        fn combine(left: &mut Self, right: &mut Self);
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Counter<T>(T);
+
+    impl<T: core::ops::AddAssign + Copy> Counter<T> {
+        fn add(left: &mut Self, right: &mut Self) {
+            left.0 += right.0;
+        }
+    }
+
+    impl Combine for u8 {
+        fn combine(left: &mut Self, right: &mut Self) {
+            *left = left.saturating_mul(*right);
+        }
+    }
+
+    #[derive(Debug, Merge, PartialEq)]
+    struct S {
+        #[merge(strategy = Counter::add)]
+        field1: Counter<u8>,
+        #[merge(strategy = Counter::<u16>::add)]
+        field2: Counter<u16>,
+        #[merge(strategy = <u8 as Combine>::combine)]
+        field3: u8,
+    }
+
+    impl S {
+        pub fn new(field1: u8, field2: u16, field3: u8) -> S {
+            S {
+                field1: Counter(field1),
+                field2: Counter(field2),
+                field3,
+            }
+        }
+    }
+
+    test(S::new(3, 3, 2), S::new(1, 1, 1), S::new(2, 2, 2));
+    test(S::new(1, 5, 255), S::new(1, 2, 16), S::new(0, 3, 16));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_struct_defined_in_macro() {
+    macro_rules! merge_struct {
+        ($name:ident { $($field:ident: $ty:ty => $strategy:path),* $(,)? }) => {
+            #[derive(Debug, Merge, PartialEq)]
+            struct $name {
+                $( #[merge(strategy = $strategy)] $field: $ty, )*
+                option: Option<u8>,
+            }
+        };
+    }
+
+    merge_struct!(S {
+        field1: Vec<u8> => ::merge2::vec::append,
+        field2: u8 => ::merge2::ord::max,
+    });
+
+    test(
+        S {
+            field1: vec![1, 2],
+            field2: 2,
+            option: Some(1),
+        },
+        S {
+            field1: vec![1],
+            field2: 1,
+            option: None,
+        },
+        S {
+            field1: vec![2],
+            field2: 2,
+            option: Some(1),
+        },
+    );
+}
+
+#[test]
+fn test_allow_noop() {
+    #[derive(Debug, Merge, PartialEq)]
+    #[merge(allow_noop)]
+    struct S {
+        #[merge(skip)]
+        field1: Option<u8>,
+        #[merge(skip)]
+        field2: Option<u8>,
+    }
+
+    test(
+        S {
+            field1: None,
+            field2: Some(1),
+        },
+        S {
+            field1: None,
+            field2: Some(1),
+        },
+        S {
+            field1: Some(2),
+            field2: Some(2),
+        },
+    );
+}
+
+#[test]
+fn test_fieldless_structs() {
+    #[derive(Debug, Merge, PartialEq)]
+    #[merge(allow_noop)]
+    struct Unit;
+
+    #[derive(Debug, Merge, PartialEq)]
+    #[merge(allow_noop)]
+    struct Tuple();
+
+    #[derive(Debug, Merge, PartialEq)]
+    #[merge(allow_noop)]
+    struct Named {}
+
+    test(Unit, Unit, Unit);
+    test(Tuple(), Tuple(), Tuple());
+    test(Named {}, Named {}, Named {});
+}
+
+#[test]
+fn test_unit_struct_post() {
+    #[derive(Debug, Merge, PartialEq)]
+    #[merge(post = post)]
+    struct Unit;
+
+    fn post(_: &mut Unit, _: &mut Unit) {
+        CALLED.with(|called| called.set(true));
+    }
+
+    thread_local! {
+        static CALLED: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+    }
+
+    test(Unit, Unit, Unit);
+    assert!(CALLED.with(std::cell::Cell::get));
+}
+
+// Should be kept in sync with the example in the README
+#[test]
+#[cfg(feature = "alloc")]
+fn test_readme_user() {
+    #[derive(Merge)]
+    struct User {
+        #[merge(skip)]
+        pub name: &'static str,
+        pub location: Option<&'static str>,
+        #[merge(strategy = ::merge2::vec::append)]
+        pub groups: Vec<&'static str>,
+    }
+
+    let defaults = || User {
+        name: "",
+        location: Some("Internet"),
+        groups: vec!["rust"],
+    };
+    let mut ferris = User {
+        name: "Ferris",
+        location: None,
+        groups: vec!["mascot"],
+    };
+    ferris.merge(&mut defaults());
+
+    assert_eq!("Ferris", ferris.name);
+    assert_eq!(Some("Internet"), ferris.location);
+    assert_eq!(vec!["mascot", "rust"], ferris.groups);
+
+    // The earlier operand wins for `Option` fields
+    let mut ferris = User {
+        name: "Ferris",
+        location: Some("Ocean"),
+        groups: vec![],
+    };
+    ferris.merge(&mut defaults());
+
+    assert_eq!("Ferris", ferris.name);
+    assert_eq!(Some("Ocean"), ferris.location);
+    assert_eq!(vec!["rust"], ferris.groups);
 }
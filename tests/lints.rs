@@ -0,0 +1,38 @@
+#![cfg(feature = "derive")]
+#![deny(clippy::pedantic)]
+
+use merge2::Merge;
+
+#[derive(Debug, Merge, PartialEq)]
+#[merge(post = post)]
+struct S<T> {
+    field1: Option<T>,
+    #[merge(strategy = ::merge2::any::overwrite_default)]
+    field2: u8,
+    #[merge(strategy = ::merge2::option::zip_with(|l: &mut u8, r: &mut u8| *l += *r))]
+    field3: Option<u8>,
+}
+
+fn post<T>(_: &mut S<T>, _: &mut S<T>) {}
+
+#[test]
+fn test_pedantic() {
+    let mut left = S {
+        field1: None,
+        field2: 0,
+        field3: Some(1),
+    };
+    left.merge(&mut S {
+        field1: Some(1),
+        field2: 2,
+        field3: Some(2),
+    });
+    assert_eq!(
+        S {
+            field1: Some(1),
+            field2: 2,
+            field3: Some(3),
+        },
+        left
+    );
+}
@@ -0,0 +1,58 @@
+use merge2::Merge;
+
+fn test<T: std::fmt::Debug + Merge + PartialEq>(expected: T, mut left: T, mut right: T) {
+    left.merge(&mut right);
+    assert_eq!(expected, left);
+}
+
+#[test]
+fn test_impl_merge() {
+    #[derive(Debug, PartialEq)]
+    struct S {
+        field1: Option<u8>,
+        field2: u8,
+        field3: Option<u8>,
+    }
+
+    merge2::impl_merge!(S {
+        field1,
+        field2 => ::merge2::ord::max,
+    });
+
+    let s = |field1, field2, field3| S {
+        field1,
+        field2,
+        field3,
+    };
+
+    test(
+        s(Some(1), 2, None),
+        s(None, 1, None),
+        s(Some(1), 2, Some(3)),
+    );
+    test(
+        s(Some(1), 2, Some(2)),
+        s(Some(1), 2, Some(2)),
+        s(Some(3), 1, Some(3)),
+    );
+}
+
+#[test]
+fn test_impl_merge_tuple() {
+    #[derive(Debug, PartialEq)]
+    struct S(Option<u8>, u8);
+
+    merge2::impl_merge!(S { 0, 1 => |l: &mut u8, r: &mut u8| *l += *r });
+
+    test(S(Some(1), 3), S(None, 1), S(Some(1), 2));
+}
+
+#[test]
+fn test_impl_merge_generic() {
+    #[derive(Debug, PartialEq)]
+    struct S<T>(Option<T>);
+
+    merge2::impl_merge!(S<u8> { 0 });
+
+    test(S(Some(1)), S(None), S(Some(1)));
+}
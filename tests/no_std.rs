@@ -0,0 +1,104 @@
+//! Checks the parts of the crate that don't require the standard library, run it with
+//! `cargo test --no-default-features --test no_std`, and with `--features alloc` for the
+//! strategies that require the `alloc` crate.
+
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+use merge2::Merge;
+
+fn merge<T>(f: fn(&mut T, &mut T), mut left: T, mut right: T) -> T {
+    f(&mut left, &mut right);
+    left
+}
+
+#[test]
+fn test_keep_left() {
+    let mut left = (1u8, -1i64, true, 'a', 1.0f64);
+    left.merge(&mut (2, -2, false, 'b', 2.0));
+    assert_eq!((1, -1, true, 'a', 1.0), left);
+}
+
+#[test]
+fn test_any() {
+    assert_eq!(2, merge(merge2::any::overwrite, 1, 2));
+    assert_eq!(2, merge(merge2::any::overwrite_default, 0, 2));
+    assert_eq!(1, merge(merge2::any::fill_default, 1, 2));
+}
+
+#[test]
+fn test_bool() {
+    assert!(merge(merge2::bool::overwrite_false, false, true));
+    assert!(!merge(merge2::bool::overwrite_true, true, false));
+}
+
+#[test]
+fn test_ord() {
+    assert_eq!(2, merge(merge2::ord::max, 1, 2));
+    assert_eq!(1, merge(merge2::ord::min, 1, 2));
+}
+
+#[test]
+fn test_option() {
+    let mut left = None;
+    left.merge(&mut Some(1));
+    assert_eq!(Some(1), left);
+
+    assert_eq!(
+        Some(2),
+        merge(merge2::option::overwrite_some, Some(1), Some(2))
+    );
+    assert_eq!(
+        Some((1, Some(2))),
+        merge(
+            merge2::option::recursive,
+            Some((1, None)),
+            Some((3, Some(2)))
+        )
+    );
+}
+
+#[test]
+#[cfg(not(feature = "option-recursive-default"))]
+fn test_option_default_keep_left() {
+    let mut left = Some(None);
+    left.merge(&mut Some(Some(1)));
+    assert_eq!(Some(None), left);
+}
+
+#[test]
+#[cfg(feature = "option-recursive-default")]
+fn test_option_default_recursive() {
+    let mut left = Some(None);
+    left.merge(&mut Some(Some(1)));
+    assert_eq!(Some(Some(1)), left);
+
+    let mut left = Some(Some(1));
+    left.merge(&mut Some(Some(2)));
+    assert_eq!(Some(Some(1)), left);
+}
+
+#[cfg(feature = "alloc")]
+mod alloc_strategies {
+    use alloc::vec;
+    use merge2::Merge;
+
+    #[test]
+    fn test_vec_prepend() {
+        let mut left = vec![3, 4];
+        let mut right = vec![1, 2];
+        merge2::vec::prepend(&mut left, &mut right);
+        assert_eq!(vec![1, 2, 3, 4], left);
+        assert!(right.is_empty());
+    }
+
+    #[test]
+    fn test_vec_merge() {
+        let mut left = vec![];
+        let mut right = vec![1, 2];
+        left.merge(&mut right);
+        assert_eq!(vec![1, 2], left);
+    }
+}
@@ -18,6 +18,26 @@ fn test_any_overwrite() {
     test(S(0), S(0), S(0));
 }
 
+#[test]
+fn test_any_overwrite_clone() {
+    #[derive(Clone, Debug, PartialEq)]
+    enum E {
+        A,
+        B(String),
+    }
+
+    #[derive(Debug, Merge, PartialEq)]
+    struct S(#[merge(strategy = ::merge2::any::overwrite_clone)] E);
+
+    test(S(E::B("b".to_owned())), S(E::A), S(E::B("b".to_owned())));
+    test(S(E::A), S(E::B("b".to_owned())), S(E::A));
+
+    let mut left = S(E::A);
+    let mut right = S(E::B("b".to_owned()));
+    left.merge(&mut right);
+    assert_eq!(left, right);
+}
+
 #[test]
 fn test_any_overwrite_default() {
     #[derive(Debug, Merge, PartialEq)]
@@ -29,6 +49,178 @@ fn test_any_overwrite_default() {
     test(S(0), S(0), S(0));
 }
 
+#[test]
+fn test_any_first_nondefault() {
+    use merge2::MergeExt;
+
+    #[derive(Debug, Merge, PartialEq)]
+    struct S(#[merge(strategy = ::merge2::any::first_nondefault)] u8);
+
+    test(S(1), S(1), S(2));
+    test(S(2), S(0), S(2));
+
+    let mut val = S(0);
+    val.merge_iter([&mut S(0), &mut S(2), &mut S(3)]);
+    assert_eq!(S(2), val);
+}
+
+#[test]
+fn test_any_fill_default() {
+    #[derive(Debug, Merge, PartialEq)]
+    struct S(#[merge(strategy = ::merge2::any::fill_default)] u8);
+
+    test(S(1), S(1), S(2));
+    test(S(2), S(0), S(2));
+    test(S(1), S(1), S(0));
+    test(S(0), S(0), S(0));
+}
+
+#[test]
+fn test_any_fill_default_both_default() {
+    // Equality ignores `tag`, so both sides compare equal to the default.
+    #[derive(Debug, Default)]
+    struct T {
+        value: u8,
+        tag: &'static str,
+    }
+
+    impl PartialEq for T {
+        fn eq(&self, other: &Self) -> bool {
+            self.value == other.value
+        }
+    }
+
+    let mut left = T {
+        value: 0,
+        tag: "left",
+    };
+    let mut right = T {
+        value: 0,
+        tag: "right",
+    };
+    ::merge2::any::overwrite_default(&mut left, &mut right);
+    assert_eq!("right", left.tag);
+
+    let mut left = T {
+        value: 0,
+        tag: "left",
+    };
+    let mut right = T {
+        value: 0,
+        tag: "right",
+    };
+    ::merge2::any::fill_default(&mut left, &mut right);
+    assert_eq!("left", left.tag);
+}
+
+#[test]
+fn test_any_prefer_nondefault() {
+    #[derive(Debug, Merge, PartialEq)]
+    struct S(#[merge(strategy = ::merge2::any::prefer_nondefault)] Option<u8>);
+
+    test(S(Some(1)), S(Some(1)), S(Some(2)));
+    test(S(Some(2)), S(None), S(Some(2)));
+    test(S(Some(1)), S(Some(1)), S(None));
+    test(S(None), S(None), S(None));
+
+    // Unlike `overwrite_default`, a right side equal to the default isn't swapped in
+    #[derive(Debug, Default)]
+    struct T(u8, &'static str);
+
+    impl PartialEq for T {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+
+    let mut left = T(0, "left");
+    ::merge2::any::prefer_nondefault(&mut left, &mut T(0, "right"));
+    assert_eq!("left", left.1);
+    ::merge2::any::overwrite_default(&mut left, &mut T(0, "right"));
+    assert_eq!("right", left.1);
+}
+
+#[test]
+fn test_any_overwrite_if_changed() {
+    use std::cell::Cell;
+
+    // Equality ignores `writes`, so a kept counter shows that `left` wasn't replaced
+    #[derive(Debug)]
+    struct Tracked {
+        value: u8,
+        writes: Cell<u8>,
+    }
+
+    impl Tracked {
+        fn new(value: u8) -> Self {
+            Tracked {
+                value,
+                writes: Cell::new(0),
+            }
+        }
+    }
+
+    impl PartialEq for Tracked {
+        fn eq(&self, other: &Self) -> bool {
+            self.value == other.value
+        }
+    }
+
+    #[derive(Debug, Merge, PartialEq)]
+    struct S(#[merge(strategy = ::merge2::any::overwrite_if_changed)] Tracked);
+
+    test(S(Tracked::new(2)), S(Tracked::new(1)), S(Tracked::new(2)));
+    test(S(Tracked::new(0)), S(Tracked::new(1)), S(Tracked::new(0)));
+
+    let mut left = S(Tracked::new(1));
+    left.0.writes.set(1);
+    left.merge(&mut S(Tracked::new(1)));
+    assert_eq!(1, left.0.writes.get());
+    left.merge(&mut S(Tracked::new(2)));
+    assert_eq!(0, left.0.writes.get());
+}
+
+#[test]
+fn test_any_overwrite_if_newer() {
+    #[derive(Debug, Default, PartialEq)]
+    struct V {
+        value: u8,
+        updated: u64,
+    }
+
+    impl merge2::Timestamped for V {
+        fn timestamp(&self) -> u64 {
+            self.updated
+        }
+    }
+
+    #[derive(Debug, Merge, PartialEq)]
+    struct S(#[merge(strategy = ::merge2::any::overwrite_if_newer)] V);
+
+    let s = |value, updated| S(V { value, updated });
+
+    test(s(2, 2), s(1, 1), s(2, 2));
+    test(s(1, 2), s(1, 2), s(2, 1));
+    test(s(1, 1), s(1, 1), s(2, 1));
+    test(s(0, 0), s(0, 0), s(0, 0));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_any_overwrite_box() {
+    use std::any::Any;
+
+    #[derive(Merge)]
+    struct S(#[merge(strategy = ::merge2::any::overwrite_box)] Box<dyn Any>);
+
+    let mut left = S(Box::new(1u8));
+    let mut right = S(Box::new("right"));
+    left.merge(&mut right);
+
+    assert_eq!(Some(&"right"), left.0.downcast_ref::<&str>());
+    assert_eq!(Some(&1u8), right.0.downcast_ref::<u8>());
+}
+
 #[test]
 fn test_any_swap() {
     #[derive(Debug, Merge, PartialEq)]
@@ -51,6 +243,57 @@ fn test_option_overwrite_none() {
     test(S(None), S(None), S(None));
 }
 
+#[test]
+fn test_option_overwrite_some() {
+    #[derive(Debug, Merge, PartialEq)]
+    struct S(#[merge(strategy = ::merge2::option::overwrite_some)] Option<u8>);
+
+    test(S(Some(2)), S(Some(1)), S(Some(2)));
+    test(S(Some(1)), S(Some(1)), S(None));
+    test(S(Some(2)), S(None), S(Some(2)));
+    test(S(None), S(None), S(None));
+}
+
+#[test]
+fn test_option_prefer_exclusive() {
+    #[derive(Debug, Merge, PartialEq)]
+    struct S(#[merge(strategy = ::merge2::option::prefer_exclusive)] Option<u8>);
+
+    test(S(Some(1)), S(Some(1)), S(Some(2)));
+    test(S(Some(1)), S(Some(1)), S(None));
+    test(S(Some(2)), S(None), S(Some(2)));
+    test(S(None), S(None), S(None));
+}
+
+#[test]
+fn test_option_try_exclusive() {
+    use merge2::option::{try_exclusive, BothSome};
+
+    let mut left = None;
+    assert_eq!(Ok(()), try_exclusive(&mut left, &mut None::<u8>));
+    assert_eq!(None, left);
+
+    let mut left = Some(1);
+    assert_eq!(Ok(()), try_exclusive(&mut left, &mut None));
+    assert_eq!(Some(1), left);
+
+    let mut left = None;
+    let mut right = Some(2);
+    assert_eq!(Ok(()), try_exclusive(&mut left, &mut right));
+    assert_eq!(Some(2), left);
+    assert_eq!(None, right);
+
+    let mut left = Some(1);
+    let mut right = Some(2);
+    assert_eq!(Err(BothSome), try_exclusive(&mut left, &mut right));
+    assert_eq!(Some(1), left);
+    assert_eq!(Some(2), right);
+    assert_eq!(
+        "both options are `Some`, but they are mutually exclusive",
+        BothSome.to_string()
+    );
+}
+
 #[test]
 #[cfg(all(feature = "num", feature = "std"))]
 fn test_option_recursive() {
@@ -66,6 +309,129 @@ fn test_option_recursive() {
     test(S(None), S(None), S(None));
 }
 
+#[test]
+fn test_option_fill_or_recursive() {
+    #[derive(Clone, Debug, Merge, PartialEq)]
+    struct Inner {
+        field1: Option<u8>,
+        field2: Option<u8>,
+    }
+
+    #[derive(Clone, Debug, Merge, PartialEq)]
+    struct S(#[merge(strategy = ::merge2::option::fill_or_recursive)] Option<Inner>);
+
+    #[derive(Debug, Merge, PartialEq)]
+    struct R(#[merge(strategy = ::merge2::option::recursive)] Option<Inner>);
+
+    let s = |field1, field2| S(Some(Inner { field1, field2 }));
+    let cases = [
+        (s(Some(1), Some(2)), s(Some(1), None), s(Some(3), Some(2))),
+        (s(Some(1), None), s(Some(1), None), S(None)),
+        (s(None, Some(2)), S(None), s(None, Some(2))),
+        (S(None), S(None), S(None)),
+    ];
+    for (expected, left, right) in cases {
+        test(R(expected.0.clone()), R(left.0.clone()), R(right.0.clone()));
+        test(expected, left, right);
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_option_recursive_boxed() {
+    #[derive(Debug, Merge, PartialEq)]
+    struct Inner {
+        field1: Option<u8>,
+        field2: Option<u8>,
+    }
+
+    #[derive(Debug, Merge, PartialEq)]
+    struct S(#[merge(strategy = ::merge2::option::recursive_boxed)] Option<Box<Inner>>);
+
+    let s = |field1, field2| S(Some(Box::new(Inner { field1, field2 })));
+
+    test(s(Some(1), Some(2)), s(Some(1), None), s(Some(3), Some(2)));
+    test(s(Some(1), None), s(Some(1), None), S(None));
+    test(s(None, Some(2)), S(None), s(None, Some(2)));
+    test(S(None), S(None), S(None));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_option_vec_append() {
+    #[derive(Debug, Merge, PartialEq)]
+    struct S(#[merge(strategy = ::merge2::option::vec_append)] Option<Vec<u8>>);
+
+    test(S(None), S(None), S(None));
+    test(S(Some(vec![1])), S(Some(vec![1])), S(None));
+    test(S(Some(vec![2])), S(None), S(Some(vec![2])));
+    test(S(Some(vec![1, 2])), S(Some(vec![1])), S(Some(vec![2])));
+    test(S(Some(vec![])), S(Some(vec![])), S(Some(vec![])));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_option_replace_some() {
+    #[derive(Debug, Merge, PartialEq)]
+    struct S(#[merge(strategy = ::merge2::option::replace_some)] Option<Vec<u8>>);
+
+    test(S(None), S(None), S(None));
+    test(S(Some(vec![1])), S(Some(vec![1])), S(None));
+    test(S(Some(vec![2])), S(None), S(Some(vec![2])));
+    test(S(Some(vec![2, 3])), S(Some(vec![1])), S(Some(vec![2, 3])));
+    test(S(Some(vec![])), S(Some(vec![1])), S(Some(vec![])));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_option_string_append() {
+    #[derive(Debug, Merge, PartialEq)]
+    struct S(#[merge(strategy = ::merge2::option::string_append)] Option<String>);
+
+    let s = |s: &str| S(Some(s.to_owned()));
+
+    test(S(None), S(None), S(None));
+    test(s("a"), s("a"), S(None));
+    test(s("b"), S(None), s("b"));
+    test(s("ab"), s("a"), s("b"));
+    test(s("b"), s(""), s("b"));
+    test(s("a"), s("a"), s(""));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_option_map_recursive() {
+    use std::collections::HashMap;
+
+    #[derive(Debug, Merge, PartialEq)]
+    struct S(#[merge(strategy = ::merge2::option::map_recursive)] Option<HashMap<u8, Option<u8>>>);
+
+    let s = |pairs: &[(u8, Option<u8>)]| S(Some(pairs.iter().copied().collect()));
+
+    test(
+        s(&[(1, Some(1)), (2, Some(4)), (3, Some(5))]),
+        s(&[(1, Some(1)), (2, None)]),
+        s(&[(1, Some(3)), (2, Some(4)), (3, Some(5))]),
+    );
+    test(s(&[(1, Some(1))]), s(&[(1, Some(1))]), S(None));
+    test(s(&[(1, Some(1))]), S(None), s(&[(1, Some(1))]));
+    test(S(None), S(None), S(None));
+}
+
+#[test]
+fn test_option_zip_with() {
+    #[derive(Debug, Merge, PartialEq)]
+    struct S(
+        #[merge(strategy = ::merge2::option::zip_with(|l: &mut u32, r: &mut u32| *l += *r))]
+        Option<u32>,
+    );
+
+    test(S(Some(3)), S(Some(1)), S(Some(2)));
+    test(S(Some(1)), S(Some(1)), S(None));
+    test(S(Some(2)), S(None), S(Some(2)));
+    test(S(None), S(None), S(None));
+}
+
 #[test]
 fn test_bool_overwrite_false() {
     #[derive(Debug, Merge, PartialEq)]
@@ -88,6 +454,26 @@ fn test_bool_overwrite_true() {
     test(S(true), S(true), S(true));
 }
 
+#[test]
+fn test_char_keep_left() {
+    #[derive(Debug, Merge, PartialEq)]
+    struct S(char);
+
+    test(S('a'), S('a'), S('b'));
+    test(S('\0'), S('\0'), S('b'));
+}
+
+#[test]
+fn test_char_overwrite_default() {
+    #[derive(Debug, Merge, PartialEq)]
+    struct S(#[merge(strategy = ::merge2::char::overwrite_default)] char);
+
+    test(S('a'), S('a'), S('b'));
+    test(S('b'), S('\0'), S('b'));
+    test(S('a'), S('a'), S('\0'));
+    test(S('\0'), S('\0'), S('\0'));
+}
+
 #[cfg(feature = "num")]
 #[test]
 fn test_num_saturating_add() {
@@ -100,6 +486,188 @@ fn test_num_saturating_add() {
     test(S(40), S(30), S(10));
 }
 
+#[test]
+fn test_saturating() {
+    use std::num::Saturating;
+
+    #[derive(Debug, Merge, PartialEq)]
+    struct S(Saturating<u8>, Saturating<i8>);
+
+    let s = |a, b| S(Saturating(a), Saturating(b));
+
+    test(s(3, -3), s(1, -1), s(2, -2));
+    test(s(u8::MAX, i8::MIN), s(200, -100), s(100, -100));
+    test(s(u8::MAX, i8::MAX), s(u8::MAX, i8::MAX), s(1, 1));
+}
+
+#[test]
+fn test_duration() {
+    use std::time::Duration;
+
+    #[derive(Debug, Merge, PartialEq)]
+    struct S(
+        #[merge(strategy = ::merge2::duration::saturating_add)] Duration,
+        #[merge(strategy = ::merge2::duration::max)] Duration,
+        #[merge(strategy = ::merge2::duration::min)] Duration,
+    );
+
+    let s = |secs| {
+        S(
+            Duration::from_secs(secs),
+            Duration::from_secs(secs),
+            Duration::from_secs(secs),
+        )
+    };
+    let d = Duration::from_secs;
+
+    test(S(d(3), d(2), d(1)), s(1), s(2));
+    test(S(d(3), d(2), d(1)), s(2), s(1));
+    test(
+        S(Duration::MAX, Duration::MAX, d(1)),
+        S(Duration::MAX, d(1), d(1)),
+        S(d(1), Duration::MAX, Duration::MAX),
+    );
+}
+
+#[test]
+fn test_bits() {
+    #[derive(Debug, Merge, PartialEq)]
+    struct S(
+        #[merge(strategy = ::merge2::bits::bitor)] u8,
+        #[merge(strategy = ::merge2::bits::bitand)] u8,
+        #[merge(strategy = ::merge2::bits::bitxor)] u8,
+    );
+
+    let s = |v| S(v, v, v);
+
+    test(S(0b1110, 0b1000, 0b0110), s(0b1100), s(0b1010));
+    test(S(0b0001, 0b0000, 0b0001), s(0b0001), s(0b0000));
+    test(S(0b1111, 0b1111, 0b0000), s(0b1111), s(0b1111));
+}
+
+#[test]
+fn test_wrappers_clamped() {
+    use merge2::wrappers::Clamped;
+
+    #[derive(Debug, Merge, PartialEq)]
+    struct S(Clamped<u8>);
+
+    let s = |value, lo, hi| S(Clamped::new(value, lo, hi));
+
+    // below
+    test(s(2, 2, 8), s(1, 0, 10), s(5, 2, 8));
+    // within
+    test(s(5, 2, 8), s(5, 0, 10), s(0, 2, 8));
+    test(s(2, 2, 8), s(2, 0, 10), s(0, 2, 8));
+    test(s(8, 2, 8), s(8, 0, 10), s(0, 2, 8));
+    // above
+    test(s(8, 2, 8), s(9, 0, 10), s(0, 2, 8));
+    // empty range
+    test(s(2, 8, 2), s(5, 0, 10), s(0, 8, 2));
+}
+
+mod float {
+    use super::test;
+    use crate::Merge;
+
+    const NAN: f64 = f64::NAN;
+
+    fn merge(f: fn(&mut f64, &mut f64), mut left: f64, mut right: f64) -> f64 {
+        f(&mut left, &mut right);
+        left
+    }
+
+    #[test]
+    fn test_max() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(#[merge(strategy = ::merge2::float::max)] f32);
+
+        test(S(2.), S(1.), S(2.));
+        test(S(2.), S(2.), S(1.));
+        test(S(-1.), S(-1.), S(-2.));
+
+        let max = ::merge2::float::max;
+        assert_eq!(1., merge(max, NAN, 1.));
+        assert_eq!(1., merge(max, 1., NAN));
+        assert!(merge(max, NAN, NAN).is_nan());
+    }
+
+    #[test]
+    fn test_min() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(#[merge(strategy = ::merge2::float::min)] f32);
+
+        test(S(1.), S(1.), S(2.));
+        test(S(1.), S(2.), S(1.));
+        test(S(-2.), S(-1.), S(-2.));
+
+        let min = ::merge2::float::min;
+        assert_eq!(1., merge(min, NAN, 1.));
+        assert_eq!(1., merge(min, 1., NAN));
+        assert!(merge(min, NAN, NAN).is_nan());
+    }
+
+    #[test]
+    fn test_max_swap_total() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(#[merge(strategy = ::merge2::float::max_swap_total)] f32);
+
+        test(S(2.), S(1.), S(2.));
+        test(S(2.), S(2.), S(1.));
+        test(S(0.), S(-0.), S(0.));
+
+        let max = ::merge2::float::max_swap_total;
+        assert!(merge(max, NAN, 1.).is_nan());
+        assert!(merge(max, 1., NAN).is_nan());
+        assert_eq!(1., merge(max, -NAN, 1.));
+        assert_eq!(1., merge(max, 1., -NAN));
+    }
+
+    #[test]
+    fn test_min_swap_total() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(#[merge(strategy = ::merge2::float::min_swap_total)] f32);
+
+        test(S(1.), S(1.), S(2.));
+        test(S(1.), S(2.), S(1.));
+        assert!(merge(::merge2::float::min_swap_total, 0., -0.).is_sign_negative());
+
+        let min = ::merge2::float::min_swap_total;
+        assert_eq!(1., merge(min, NAN, 1.));
+        assert_eq!(1., merge(min, 1., NAN));
+        assert!(merge(min, -NAN, 1.).is_nan());
+        assert!(merge(min, 1., -NAN).is_nan());
+    }
+
+    #[test]
+    fn test_add() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(#[merge(strategy = ::merge2::float::add)] f32);
+
+        test(S(3.), S(1.), S(2.));
+        test(S(0.), S(-1.), S(1.));
+
+        let add = ::merge2::float::add;
+        assert!(merge(add, NAN, 1.).is_nan());
+        assert!(merge(add, 1., NAN).is_nan());
+        assert!(merge(add, NAN, NAN).is_nan());
+    }
+
+    #[test]
+    fn test_overwrite_nan() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(#[merge(strategy = ::merge2::float::overwrite_nan)] f32);
+
+        test(S(1.), S(1.), S(2.));
+        test(S(f32::INFINITY), S(f32::NAN), S(f32::INFINITY));
+
+        let overwrite_nan = ::merge2::float::overwrite_nan;
+        assert_eq!(1., merge(overwrite_nan, NAN, 1.));
+        assert_eq!(1., merge(overwrite_nan, 1., NAN));
+        assert!(merge(overwrite_nan, NAN, NAN).is_nan());
+    }
+}
+
 #[test]
 fn test_ord_max() {
     #[derive(Debug, Merge, PartialEq)]
@@ -126,35 +694,150 @@ fn test_ord_min() {
     test(S(11), S(33), S(11));
 }
 
-#[cfg(feature = "std")]
-mod string {
+mod array {
     use super::test;
     use crate::Merge;
 
     #[test]
-    fn test_str_overwrite_empty() {
+    fn test_recursive() {
         #[derive(Debug, Merge, PartialEq)]
-        struct S<'a>(&'a str);
+        struct S(
+            [Option<u8>; 3],
+            #[merge(strategy = ::merge2::array::recursive)] [Option<u8>; 3],
+        );
 
-        test(S(""), S(""), S(""));
-        test(S("1"), S(""), S("1"));
-        test(S("0"), S("0"), S(""));
-        test(S("0"), S("0"), S("1"));
-        test(S("255"), S("255"), S("10"));
+        let s = |v| S(v, v);
+
+        test(
+            s([Some(1), Some(2), Some(6)]),
+            s([Some(1), None, Some(6)]),
+            s([Some(4), Some(2), None]),
+        );
+        test(s([None; 3]), s([None; 3]), s([None; 3]));
     }
 
     #[test]
-    fn test_string_overwrite_empty() {
+    fn test_overwrite_if_all_default() {
         #[derive(Debug, Merge, PartialEq)]
-        struct S(String);
+        struct S(#[merge(strategy = ::merge2::array::overwrite_if_all_default)] [Option<u8>; 3]);
 
-        test(S("".to_owned()), S("".to_owned()), S("".to_owned()));
-        test(S("1".to_owned()), S("".to_owned()), S("1".to_owned()));
+        test(
+            S([Some(1), None, Some(6)]),
+            S([Some(1), None, Some(6)]),
+            S([Some(4), Some(2), None]),
+        );
+        test(
+            S([Some(4), Some(2), None]),
+            S([None; 3]),
+            S([Some(4), Some(2), None]),
+        );
+        test(S([None; 3]), S([None; 3]), S([None; 3]));
+    }
+}
+
+mod tuple {
+    use super::test;
+    use crate::Merge;
+
+    #[test]
+    fn test_unit() {
+        test((), (), ());
+    }
+
+    #[test]
+    fn test_one() {
+        test((Some(1),), (Some(1),), (Some(2),));
+        test((Some(2),), (None,), (Some(2),));
+    }
+
+    #[test]
+    fn test_twelve() {
+        type T = Option<u8>;
+
+        // All elements of a tuple field are merged
+        #[derive(Debug, Merge, PartialEq)]
+        struct S((T, T, T, T, T, T, T, T, T, T, T, T));
+
+        let n = None;
+        let (a, b) = (Some(1), Some(2));
+        test(
+            S((a, b, b, b, b, b, b, b, b, b, b, a)),
+            S((a, n, n, n, n, n, n, n, n, n, n, a)),
+            S((b, b, b, b, b, b, b, b, b, b, b, b)),
+        );
+    }
+
+    #[test]
+    fn test_sixteen() {
+        let n = None;
+        let (a, b) = (Some(1), Some(2));
+        let mut left = (a, n, n, n, n, n, n, n, n, n, n, n, n, n, n, a);
+        let mut right = (b, b, b, b, b, b, b, b, b, b, b, b, b, b, b, b);
+        left.merge(&mut right);
+
+        assert_eq!((a, b, b, b), (left.0, left.1, left.2, left.3));
+        assert_eq!((b, b, b, b), (left.4, left.5, left.6, left.7));
+        assert_eq!((b, b, b, b), (left.8, left.9, left.10, left.11));
+        assert_eq!((b, b, b, a), (left.12, left.13, left.14, left.15));
+    }
+}
+
+#[cfg(feature = "std")]
+mod string {
+    use super::test;
+    use crate::Merge;
+
+    #[test]
+    fn test_str_overwrite_empty() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S<'a>(&'a str);
+
+        test(S(""), S(""), S(""));
+        test(S("1"), S(""), S("1"));
+        test(S("0"), S("0"), S(""));
+        test(S("0"), S("0"), S("1"));
+        test(S("255"), S("255"), S("10"));
+    }
+
+    #[test]
+    fn test_string_overwrite_empty() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(String);
+
+        test(S("".to_owned()), S("".to_owned()), S("".to_owned()));
+        test(S("1".to_owned()), S("".to_owned()), S("1".to_owned()));
+        test(S("0".to_owned()), S("0".to_owned()), S("".to_owned()));
+        test(S("0".to_owned()), S("0".to_owned()), S("1".to_owned()));
+        test(S("255".to_owned()), S("255".to_owned()), S("10".to_owned()));
+    }
+
+    #[test]
+    fn test_string_overwrite_if_empty() {
+        #[derive(Debug, Merge, PartialEq)]
+        #[merge(strategy = ::merge2::string::append)]
+        struct S(#[merge(strategy = ::merge2::string::overwrite_if_empty)] String);
+
+        test(S("".to_owned()), S("".to_owned()), S("".to_owned()));
+        test(S("1".to_owned()), S("".to_owned()), S("1".to_owned()));
         test(S("0".to_owned()), S("0".to_owned()), S("".to_owned()));
         test(S("0".to_owned()), S("0".to_owned()), S("1".to_owned()));
         test(S("255".to_owned()), S("255".to_owned()), S("10".to_owned()));
     }
 
+    #[test]
+    fn test_string_overwrite_if_blank() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(#[merge(strategy = ::merge2::string::overwrite_if_blank)] String);
+
+        let s = |v: &str| S(v.to_owned());
+
+        test(s("right"), s(""), s("right"));
+        test(s("right"), s("   "), s("right"));
+        test(s("right"), s("\t\n"), s("right"));
+        test(s(" left "), s(" left "), s("right"));
+        test(s(""), s("  "), s(""));
+    }
+
     #[test]
     fn test_string_append() {
         #[derive(Debug, Merge, PartialEq)]
@@ -181,6 +864,41 @@ mod string {
         );
     }
 
+    #[test]
+    fn test_string_clamp_len() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(#[merge(strategy = ::merge2::string::clamp_len)] String);
+
+        let s = |v: &str| S(v.to_owned());
+
+        test(s("abc"), s("abcdef"), s("xyz"));
+        test(s("ab"), s("ab"), s("xyz"));
+        test(s(""), s("abc"), s(""));
+        test(s(""), s(""), s("xyz"));
+        // "é" takes 2 bytes, a cap of 2 would split it
+        test(s("a"), s("aéb"), s("xy"));
+        test(s("aé"), s("aéb"), s("xyz"));
+    }
+
+    #[test]
+    fn test_string_append_lines_unique() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(#[merge(strategy = ::merge2::string::append_lines_unique)] String);
+
+        let s = |v: &str| S(v.to_owned());
+
+        test(s(""), s(""), s(""));
+        test(s("a\n"), s(""), s("a"));
+        test(s("a"), s("a"), s(""));
+        test(s("a"), s("a"), s("a\n"));
+        test(s("a\nb\n"), s("a"), s("b"));
+        test(s("a\nb\nc\n"), s("a\nb\n"), s("c\na\n"));
+        test(s("a\nb\nc\nd\n"), s("a\nb\n"), s("c\nd\n"));
+        test(s("a\nb\nc\n"), s("a\nb\n"), s("c\nc\nb\n"));
+        test(s("a\r\nb\r\nc\n"), s("a\r\nb\r\n"), s("b\r\nc\r\n"));
+        test(s("a\nb\r\n"), s("a\nb\r\n"), s("a\r\nb\n"));
+    }
+
     #[test]
     fn test_string_prepend() {
         #[derive(Debug, Merge, PartialEq)]
@@ -208,7 +926,7 @@ mod string {
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(feature = "alloc")]
 mod vec {
     use super::test;
     use crate::Merge;
@@ -225,6 +943,19 @@ mod vec {
         test(S(vec![255]), S(vec![255]), S(vec![10]));
     }
 
+    #[test]
+    fn test_overwrite_if_empty() {
+        #[derive(Debug, Merge, PartialEq)]
+        #[merge(strategy = ::merge2::vec::append)]
+        struct S(#[merge(strategy = ::merge2::vec::overwrite_if_empty)] Vec<u8>);
+
+        test(S(vec![]), S(vec![]), S(vec![]));
+        test(S(vec![1]), S(vec![]), S(vec![1]));
+        test(S(vec![0]), S(vec![0]), S(vec![]));
+        test(S(vec![0]), S(vec![0]), S(vec![1]));
+        test(S(vec![255]), S(vec![255]), S(vec![10]));
+    }
+
     #[test]
     fn test_append() {
         #[derive(Debug, Merge, PartialEq)]
@@ -239,6 +970,165 @@ mod vec {
         test(S(vec![3, 4, 0, 1, 2]), S(vec![3, 4]), S(vec![0, 1, 2]));
     }
 
+    #[test]
+    fn test_append_copy() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(#[merge(strategy = ::merge2::vec::append_copy)] Vec<u8>);
+
+        test(S(vec![]), S(vec![]), S(vec![]));
+        test(S(vec![1]), S(vec![]), S(vec![1]));
+        test(S(vec![0]), S(vec![0]), S(vec![]));
+        test(S(vec![0, 1, 2, 3, 4]), S(vec![0, 1, 2]), S(vec![3, 4]));
+    }
+
+    #[test]
+    fn test_append_copy_keeps_buffers() {
+        let mut left = Vec::with_capacity(4);
+        let mut right = Vec::with_capacity(16);
+        right.extend_from_slice(&[1u8, 2]);
+        merge2::vec::append_copy(&mut left, &mut right);
+
+        assert_eq!(vec![1, 2], left);
+        assert!(right.is_empty());
+        assert!(right.capacity() >= 16);
+    }
+
+    #[test]
+    fn test_append_turbofish() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(#[merge(strategy = ::merge2::vec::append::<u8>)] Vec<u8>);
+
+        test(S(vec![]), S(vec![]), S(vec![]));
+        test(S(vec![1]), S(vec![]), S(vec![1]));
+        test(S(vec![0, 1]), S(vec![0]), S(vec![1]));
+    }
+
+    #[test]
+    fn test_append_large() {
+        let mut left: Vec<u32> = (0..100_000).collect();
+        let mut right: Vec<u32> = (100_000..250_000).collect();
+        ::merge2::vec::append(&mut left, &mut right);
+        assert!(left.iter().copied().eq(0..250_000));
+        assert!(right.is_empty());
+
+        let mut left = Vec::with_capacity(250_000);
+        left.extend(0..100_000);
+        let mut right: Vec<u32> = (100_000..250_000).collect();
+        let ptr = left.as_ptr();
+        ::merge2::vec::append(&mut left, &mut right);
+        assert!(left.iter().copied().eq(0..250_000));
+        assert_eq!(ptr, left.as_ptr());
+    }
+
+    #[test]
+    fn test_append_dedup() {
+        fn dedup(left: &mut Vec<u8>, _: &mut Vec<u8>) {
+            left.dedup();
+        }
+
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(
+            #[merge(strategy = ::merge2::any::sequence(::merge2::vec::append, dedup))] Vec<u8>,
+        );
+
+        test(S(vec![]), S(vec![]), S(vec![]));
+        test(S(vec![1]), S(vec![]), S(vec![1, 1]));
+        test(S(vec![0, 1]), S(vec![0]), S(vec![1]));
+        test(S(vec![0, 1, 2]), S(vec![0, 1]), S(vec![1, 2, 2]));
+        test(S(vec![0, 1, 0]), S(vec![0, 1]), S(vec![0]));
+    }
+
+    #[test]
+    fn test_clamp_len() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(#[merge(strategy = ::merge2::vec::clamp_len)] Vec<u8>);
+
+        test(S(vec![1, 2]), S(vec![1, 2, 3]), S(vec![0, 0]));
+        test(S(vec![1]), S(vec![1]), S(vec![0, 0]));
+        test(S(vec![1, 2]), S(vec![1, 2]), S(vec![0, 0]));
+        test(S(vec![]), S(vec![1, 2]), S(vec![]));
+    }
+
+    #[test]
+    fn test_interleave() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(#[merge(strategy = ::merge2::vec::interleave)] Vec<u8>);
+
+        test(S(vec![1, 2, 3, 4]), S(vec![1, 3]), S(vec![2, 4]));
+        test(S(vec![1, 2, 3, 5, 7]), S(vec![1, 3, 5, 7]), S(vec![2]));
+        test(S(vec![1, 2, 3, 4, 6]), S(vec![1, 3]), S(vec![2, 4, 6]));
+        test(S(vec![1, 2]), S(vec![1, 2]), S(vec![]));
+        test(S(vec![1, 2]), S(vec![]), S(vec![1, 2]));
+        test(S(vec![]), S(vec![]), S(vec![]));
+    }
+
+    #[test]
+    fn test_union_sorted() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(#[merge(strategy = ::merge2::vec::union_sorted)] Vec<u8>);
+
+        test(
+            S(vec![1, 2, 3, 5, 8]),
+            S(vec![1, 3, 5]),
+            S(vec![2, 3, 5, 8]),
+        );
+        test(S(vec![1, 2, 3, 4]), S(vec![3, 4]), S(vec![1, 2]));
+        test(S(vec![1, 2]), S(vec![1, 2]), S(vec![1, 2]));
+        test(S(vec![1, 2]), S(vec![]), S(vec![1, 2]));
+        test(S(vec![1, 2]), S(vec![1, 2]), S(vec![]));
+    }
+
+    #[test]
+    fn test_merge_assoc() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(#[merge(strategy = ::merge2::vec::merge_assoc)] Vec<(&'static str, Option<u8>)>);
+
+        test(S(vec![]), S(vec![]), S(vec![]));
+        test(S(vec![("a", Some(1))]), S(vec![]), S(vec![("a", Some(1))]));
+        test(S(vec![("a", Some(1))]), S(vec![("a", Some(1))]), S(vec![]));
+        test(
+            S(vec![("a", Some(1))]),
+            S(vec![("a", Some(1))]),
+            S(vec![("a", Some(2))]),
+        );
+        test(
+            S(vec![("a", Some(2)), ("b", Some(1)), ("c", Some(3))]),
+            S(vec![("a", None), ("b", Some(1))]),
+            S(vec![("c", Some(3)), ("a", Some(2)), ("b", Some(2))]),
+        );
+    }
+
+    #[test]
+    fn test_merge_by_key() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct Item {
+            #[merge(skip)]
+            id: u8,
+            value: Option<u8>,
+        }
+
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(#[merge(strategy = ::merge2::vec::merge_by_key(|item: &Item| item.id))] Vec<Item>);
+
+        let s = |items: &[(u8, Option<u8>)]| {
+            S(items
+                .iter()
+                .map(|&(id, value)| Item { id, value })
+                .collect())
+        };
+
+        test(s(&[]), s(&[]), s(&[]));
+        test(s(&[(1, Some(1))]), s(&[]), s(&[(1, Some(1))]));
+        test(s(&[(1, Some(1))]), s(&[(1, Some(1))]), s(&[]));
+        test(s(&[(1, Some(1))]), s(&[(1, Some(1))]), s(&[(1, Some(2))]));
+        test(s(&[(1, Some(2))]), s(&[(1, None)]), s(&[(1, Some(2))]));
+        test(
+            s(&[(1, Some(1)), (2, Some(2)), (3, Some(3))]),
+            s(&[(1, Some(1)), (2, None)]),
+            s(&[(3, Some(3)), (2, Some(2)), (1, Some(4))]),
+        );
+    }
+
     #[test]
     fn test_prepend() {
         #[derive(Debug, Merge, PartialEq)]
@@ -254,6 +1144,98 @@ mod vec {
     }
 }
 
+#[cfg(feature = "alloc")]
+mod boxed_slice {
+    use super::test;
+    use crate::Merge;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_overwrite_empty() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(Box<[u8]>, Rc<[u8]>);
+
+        let s = |a: &[u8], b: &[u8]| S(a.into(), b.into());
+
+        test(s(&[1], &[2]), s(&[], &[]), s(&[1], &[2]));
+        test(s(&[1], &[2]), s(&[1], &[2]), s(&[3], &[4]));
+        test(s(&[1], &[2]), s(&[1], &[2]), s(&[], &[]));
+    }
+
+    #[test]
+    fn test_append() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(#[merge(strategy = ::merge2::boxed_slice::append)] Box<[u8]>);
+
+        let s = |a: &[u8]| S(a.into());
+
+        test(s(&[1, 2, 3, 4]), s(&[1, 2]), s(&[3, 4]));
+        test(s(&[1, 2]), s(&[1, 2]), s(&[]));
+        test(s(&[3, 4]), s(&[]), s(&[3, 4]));
+        test(s(&[]), s(&[]), s(&[]));
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod cow {
+    use super::test;
+    use crate::Merge;
+    use std::borrow::Cow;
+
+    #[test]
+    fn test_overwrite_empty() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S<'a>(Cow<'a, [u8]>);
+
+        test(
+            S(Cow::Owned(vec![1])),
+            S(Cow::Borrowed(&[])),
+            S(Cow::Owned(vec![1])),
+        );
+        test(
+            S(Cow::Borrowed(&[1])),
+            S(Cow::Borrowed(&[1])),
+            S(Cow::Borrowed(&[2])),
+        );
+        test(
+            S(Cow::Borrowed(&[1])),
+            S(Cow::Borrowed(&[1])),
+            S(Cow::Borrowed(&[])),
+        );
+    }
+
+    #[test]
+    fn test_append() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S<'a>(#[merge(strategy = ::merge2::cow::append)] Cow<'a, [u8]>);
+
+        test(
+            S(Cow::Borrowed(&[1, 2])),
+            S(Cow::Borrowed(&[1])),
+            S(Cow::Owned(vec![2])),
+        );
+        test(
+            S(Cow::Borrowed(&[1, 2])),
+            S(Cow::Owned(vec![1])),
+            S(Cow::Borrowed(&[2])),
+        );
+        test(
+            S(Cow::Borrowed(&[2])),
+            S(Cow::Borrowed(&[])),
+            S(Cow::Borrowed(&[2])),
+        );
+
+        let (mut left, mut right) = (Cow::Borrowed(&[1u8][..]), Cow::Borrowed(&[][..]));
+        merge2::cow::append(&mut left, &mut right);
+        assert!(matches!(left, Cow::Borrowed(_)));
+
+        let (mut left, mut right) = (Cow::Borrowed(&[1u8][..]), Cow::Borrowed(&[2][..]));
+        merge2::cow::append(&mut left, &mut right);
+        assert!(matches!(left, Cow::Owned(_)));
+        assert!(right.is_empty());
+    }
+}
+
 #[cfg(feature = "std")]
 mod hashmap {
     use super::test;
@@ -369,6 +1351,37 @@ mod hashmap {
         );
     }
 
+    #[test]
+    fn test_recursive_deep() {
+        type M = HashMap<u8, HashMap<u8, Option<u8>>>;
+
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(
+            #[merge(strategy = ::merge2::hashmap::recursive)] M,
+            #[merge(strategy = ::merge2::hashmap::recursive_deep)] M,
+        );
+
+        let s = |v: M| S(v.clone(), v);
+
+        test(
+            S(
+                map! {1 => map! {1 => Some(1)}, 2 => map! {1 => None}},
+                map! {1 => map! {1 => Some(1), 2 => Some(3)}, 2 => map! {1 => None}},
+            ),
+            s(map! {1 => map! {1 => Some(1)}}),
+            s(map! {1 => map! {1 => Some(2), 2 => Some(3)}, 2 => map! {1 => None}}),
+        );
+        test(
+            S(
+                map! {1 => map! {1 => None}},
+                map! {1 => map! {1 => Some(2)}},
+            ),
+            s(map! {1 => map! {1 => None}}),
+            s(map! {1 => map! {1 => Some(2)}}),
+        );
+        test(s(M::new()), s(M::new()), s(M::new()));
+    }
+
     #[test]
     #[cfg(feature = "num")]
     fn test_intersection() {
@@ -409,4 +1422,802 @@ mod hashmap {
             S(map! {1 => N(2)}),
         );
     }
+
+    #[test]
+    fn test_merge_if_key() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(
+            #[merge(strategy = ::merge2::hashmap::merge_if_key(|k: &u8| *k < 10))]
+            HashMap<u8, Option<u8>>,
+        );
+
+        test(
+            S(HashMap::default()),
+            S(HashMap::default()),
+            S(HashMap::default()),
+        );
+        test(
+            S(map! {1 => Some(2)}),
+            S(HashMap::default()),
+            S(map! {1 => Some(2), 10 => Some(2)}),
+        );
+        test(
+            S(map! {1 => Some(1), 10 => None}),
+            S(map! {1 => Some(1), 10 => None}),
+            S(map! {1 => Some(2), 10 => Some(2)}),
+        );
+        test(
+            S(map! {1 => Some(2), 2 => Some(2)}),
+            S(map! {1 => None}),
+            S(map! {1 => Some(2), 2 => Some(2), 11 => Some(2)}),
+        );
+    }
+
+    #[test]
+    fn test_merge_normalized() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(
+            #[merge(strategy = ::merge2::hashmap::merge_normalized(|k: &String| k.to_lowercase()))]
+            HashMap<String, Option<u8>>,
+        );
+
+        let s = |pairs: &[(&str, Option<u8>)]| {
+            S(pairs.iter().map(|(k, v)| (k.to_string(), *v)).collect())
+        };
+
+        test(
+            s(&[("host", Some(1)), ("port", Some(3)), ("user", Some(4))]),
+            s(&[("Host", Some(1)), ("PORT", None)]),
+            s(&[("HOST", Some(2)), ("port", Some(3)), ("User", Some(4))]),
+        );
+        test(s(&[("host", Some(1))]), s(&[("Host", Some(1))]), s(&[]));
+    }
+
+    #[test]
+    fn test_resolve() {
+        // Keys below 10 keep the larger value, other keys keep the value of `right`
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(
+            #[merge(strategy = ::merge2::hashmap::resolve(|k: &u8, l: &u8, r: &u8| *k < 10 && l > r))]
+             HashMap<u8, u8>,
+        );
+
+        test(
+            S(map! {1 => 5, 2 => 6, 10 => 2, 11 => 3}),
+            S(map! {1 => 5, 2 => 1, 10 => 1, 11 => 5}),
+            S(map! {1 => 2, 2 => 6, 10 => 2, 11 => 3}),
+        );
+        test(S(map! {1 => 1, 2 => 2}), S(map! {1 => 1}), S(map! {2 => 2}));
+    }
+
+    #[test]
+    fn test_retain_common() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(#[merge(strategy = ::merge2::hashmap::retain_common)] HashMap<u8, u8>);
+
+        test(
+            S(HashMap::default()),
+            S(HashMap::default()),
+            S(HashMap::default()),
+        );
+        test(
+            S(HashMap::default()),
+            S(HashMap::default()),
+            S(map! {1 => 2}),
+        );
+        test(
+            S(HashMap::default()),
+            S(map! {1 => 1}),
+            S(HashMap::default()),
+        );
+        test(S(map! {1 => 1}), S(map! {1 => 1}), S(map! {1 => 2}));
+        test(
+            S(map! {1 => 1}),
+            S(map! {0 => 0, 1 => 1}),
+            S(map! {1 => 2, 2 => 2}),
+        );
+    }
+
+    #[test]
+    fn test_remove_right() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(#[merge(strategy = ::merge2::hashmap::remove_right)] HashMap<u8, u8>);
+
+        test(
+            S(HashMap::default()),
+            S(HashMap::default()),
+            S(HashMap::default()),
+        );
+        test(
+            S(HashMap::default()),
+            S(HashMap::default()),
+            S(map! {1 => 2}),
+        );
+        test(S(map! {1 => 1}), S(map! {1 => 1}), S(HashMap::default()));
+        test(S(HashMap::default()), S(map! {1 => 1}), S(map! {1 => 2}));
+        test(
+            S(map! {0 => 0}),
+            S(map! {0 => 0, 1 => 1}),
+            S(map! {1 => 2, 2 => 2}),
+        );
+    }
+
+    #[test]
+    fn test_custom_hasher() {
+        use std::collections::hash_map::RandomState;
+        use std::hash::BuildHasher;
+
+        // Doesn't implement `Default`
+        struct Hasher(RandomState);
+
+        impl BuildHasher for Hasher {
+            type Hasher = <RandomState as BuildHasher>::Hasher;
+
+            fn build_hasher(&self) -> Self::Hasher {
+                self.0.build_hasher()
+            }
+        }
+
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(
+            HashMap<u8, u8, Hasher>,
+            #[merge(strategy = ::merge2::hashmap::merge)] HashMap<u8, u8, Hasher>,
+        );
+
+        let map = |pairs: &[(u8, u8)]| {
+            let mut map = HashMap::with_hasher(Hasher(RandomState::new()));
+            map.extend(pairs.iter().copied());
+            map
+        };
+        let s = |a, b| S(map(a), map(b));
+
+        test(
+            s(&[(1, 2)], &[(1, 1), (2, 2)]),
+            s(&[], &[(1, 1)]),
+            s(&[(1, 2)], &[(1, 2), (2, 2)]),
+        );
+        test(
+            s(&[(1, 1)], &[(1, 1)]),
+            s(&[(1, 1)], &[(1, 1)]),
+            s(&[(1, 2)], &[]),
+        );
+    }
+}
+
+#[cfg(feature = "std")]
+mod hashset {
+    use super::test;
+    use crate::Merge;
+    use std::collections::HashSet;
+
+    macro_rules! set {
+        ($( $val: expr ),* $(,)*) => {{
+            let mut set = HashSet::default();
+            $( set.insert($val); )*
+            set
+        }}
+    }
+
+    #[test]
+    fn test_union_default() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(HashSet<u8>);
+
+        test(
+            S(HashSet::default()),
+            S(HashSet::default()),
+            S(HashSet::default()),
+        );
+        test(S(set! {1}), S(HashSet::default()), S(set! {1}));
+        test(S(set! {1}), S(set! {1}), S(HashSet::default()));
+        test(S(set! {1}), S(set! {1}), S(set! {1}));
+        test(S(set! {0, 1, 2}), S(set! {0, 1}), S(set! {1, 2}));
+    }
+
+    #[test]
+    fn test_union() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(#[merge(strategy = ::merge2::hashset::union)] HashSet<u8>);
+
+        test(
+            S(HashSet::default()),
+            S(HashSet::default()),
+            S(HashSet::default()),
+        );
+        test(S(set! {1}), S(HashSet::default()), S(set! {1}));
+        test(S(set! {0, 1, 2}), S(set! {0, 1}), S(set! {1, 2}));
+    }
+
+    #[test]
+    fn test_intersection() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(#[merge(strategy = ::merge2::hashset::intersection)] HashSet<u8>);
+
+        test(
+            S(HashSet::default()),
+            S(HashSet::default()),
+            S(HashSet::default()),
+        );
+        test(S(HashSet::default()), S(HashSet::default()), S(set! {1}));
+        test(S(HashSet::default()), S(set! {1}), S(HashSet::default()));
+        test(S(set! {1}), S(set! {0, 1}), S(set! {1, 2}));
+    }
+
+    #[test]
+    fn test_difference() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(#[merge(strategy = ::merge2::hashset::difference)] HashSet<u8>);
+
+        test(
+            S(HashSet::default()),
+            S(HashSet::default()),
+            S(HashSet::default()),
+        );
+        test(S(HashSet::default()), S(HashSet::default()), S(set! {1}));
+        test(S(set! {1}), S(set! {1}), S(HashSet::default()));
+        test(S(set! {0}), S(set! {0, 1}), S(set! {1, 2}));
+    }
+
+    #[test]
+    fn test_custom_hasher() {
+        use std::collections::hash_map::RandomState;
+
+        let mut left: HashSet<u8, RandomState> = set! {0};
+        let mut right = set! {1};
+        left.merge(&mut right);
+        assert_eq!(set! {0, 1}, left);
+        assert!(right.is_empty());
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod btreemap {
+    use super::test;
+    use crate::Merge;
+    use std::collections::BTreeMap;
+
+    fn map<V, const N: usize>(entries: [(u8, V); N]) -> BTreeMap<u8, V> {
+        BTreeMap::from(entries)
+    }
+
+    #[test]
+    fn test_overwrite_empty() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(BTreeMap<u8, u8>);
+
+        test(S(map([])), S(map([])), S(map([])));
+        test(S(map([(1, 2)])), S(map([])), S(map([(1, 2)])));
+        test(S(map([(1, 1)])), S(map([(1, 1)])), S(map([])));
+        test(S(map([(1, 1)])), S(map([(1, 1)])), S(map([(1, 2)])));
+        test(S(map([(1, 1)])), S(map([(1, 1)])), S(map([(2, 2)])));
+    }
+
+    #[test]
+    fn test_merge() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(#[merge(strategy = ::merge2::btreemap::merge)] BTreeMap<u8, u8>);
+
+        test(S(map([])), S(map([])), S(map([])));
+        test(S(map([(1, 2)])), S(map([])), S(map([(1, 2)])));
+        test(S(map([(1, 1)])), S(map([(1, 1)])), S(map([])));
+        test(S(map([(1, 1)])), S(map([(1, 1)])), S(map([(1, 2)])));
+        test(S(map([(0, 1), (1, 2)])), S(map([(0, 1)])), S(map([(1, 2)])));
+    }
+
+    #[test]
+    fn test_replace() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(#[merge(strategy = ::merge2::btreemap::replace)] BTreeMap<u8, u8>);
+
+        test(S(map([])), S(map([])), S(map([])));
+        test(S(map([(1, 2)])), S(map([])), S(map([(1, 2)])));
+        test(S(map([(1, 1)])), S(map([(1, 1)])), S(map([])));
+        test(S(map([(1, 2)])), S(map([(1, 1)])), S(map([(1, 2)])));
+        test(S(map([(0, 1), (1, 2)])), S(map([(0, 1)])), S(map([(1, 2)])));
+    }
+
+    #[test]
+    fn test_recursive() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(#[merge(strategy = ::merge2::btreemap::recursive)] BTreeMap<u8, Option<u8>>);
+
+        test(S(map([])), S(map([])), S(map([])));
+        test(S(map([(1, Some(2))])), S(map([])), S(map([(1, Some(2))])));
+        test(S(map([(1, Some(1))])), S(map([(1, Some(1))])), S(map([])));
+        test(
+            S(map([(1, Some(1))])),
+            S(map([(1, Some(1))])),
+            S(map([(1, Some(2))])),
+        );
+        test(
+            S(map([(1, Some(2))])),
+            S(map([(1, None)])),
+            S(map([(1, Some(2))])),
+        );
+        test(
+            S(map([(0, Some(1)), (1, None)])),
+            S(map([(0, Some(1))])),
+            S(map([(1, None)])),
+        );
+    }
+
+    #[test]
+    fn test_intersection() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(#[merge(strategy = ::merge2::btreemap::intersection)] BTreeMap<u8, Option<u8>>);
+
+        test(S(map([])), S(map([])), S(map([])));
+        test(S(map([])), S(map([])), S(map([(1, Some(2))])));
+        test(S(map([(1, Some(1))])), S(map([(1, Some(1))])), S(map([])));
+        test(
+            S(map([(1, Some(2))])),
+            S(map([(1, None)])),
+            S(map([(1, Some(2))])),
+        );
+        test(
+            S(map([(0, Some(1))])),
+            S(map([(0, Some(1))])),
+            S(map([(1, Some(2))])),
+        );
+    }
+
+    #[test]
+    fn test_ordered() {
+        let mut left = map([(3, 3), (1, 1)]);
+        ::merge2::btreemap::merge(&mut left, &mut map([(2, 2), (0, 0)]));
+        assert_eq!(
+            vec![(0, 0), (1, 1), (2, 2), (3, 3)],
+            left.into_iter().collect::<Vec<_>>()
+        );
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod btreeset {
+    use super::test;
+    use crate::Merge;
+    use std::collections::BTreeSet;
+
+    fn set<const N: usize>(values: [u8; N]) -> BTreeSet<u8> {
+        BTreeSet::from(values)
+    }
+
+    #[test]
+    fn test_overwrite_empty() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(BTreeSet<u8>);
+
+        test(S(set([])), S(set([])), S(set([])));
+        test(S(set([1, 2])), S(set([])), S(set([1, 2])));
+        test(S(set([1])), S(set([1])), S(set([])));
+        test(S(set([0, 1])), S(set([0, 1])), S(set([1, 2])));
+        test(S(set([0, 1])), S(set([0, 1])), S(set([2, 3])));
+    }
+
+    #[test]
+    fn test_union() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(#[merge(strategy = ::merge2::btreeset::union)] BTreeSet<u8>);
+
+        test(S(set([])), S(set([])), S(set([])));
+        test(S(set([1])), S(set([])), S(set([1])));
+        test(S(set([1])), S(set([1])), S(set([])));
+        test(S(set([0, 1, 2])), S(set([0, 1])), S(set([1, 2])));
+        test(S(set([0, 1, 2, 3])), S(set([0, 1])), S(set([2, 3])));
+
+        let mut left = set([3, 1]);
+        ::merge2::btreeset::union(&mut left, &mut set([2, 3, 0]));
+        assert_eq!(vec![0, 1, 2, 3], left.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_intersection() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(#[merge(strategy = ::merge2::btreeset::intersection)] BTreeSet<u8>);
+
+        test(S(set([])), S(set([])), S(set([])));
+        test(S(set([])), S(set([])), S(set([1])));
+        test(S(set([])), S(set([1])), S(set([])));
+        test(S(set([1])), S(set([0, 1])), S(set([1, 2])));
+        test(S(set([])), S(set([0, 1])), S(set([2, 3])));
+    }
+
+    #[test]
+    fn test_difference() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(#[merge(strategy = ::merge2::btreeset::difference)] BTreeSet<u8>);
+
+        test(S(set([])), S(set([])), S(set([])));
+        test(S(set([])), S(set([])), S(set([1])));
+        test(S(set([1])), S(set([1])), S(set([])));
+        test(S(set([0])), S(set([0, 1])), S(set([1, 2])));
+        test(S(set([0, 1])), S(set([0, 1])), S(set([2, 3])));
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod registry {
+    use merge2::registry::StrategyRegistry;
+
+    #[test]
+    fn test_register() {
+        let mut registry = StrategyRegistry::<Vec<u8>>::new();
+        assert!(registry.get("append").is_none());
+        registry.register("append", ::merge2::vec::append);
+
+        let mut left = vec![1];
+        let mut right = vec![2];
+        assert!(registry.merge("append", &mut left, &mut right));
+        assert_eq!(vec![1, 2], left);
+        assert!(right.is_empty());
+
+        let mut right = vec![3];
+        assert!(!registry.merge("prepend", &mut left, &mut right));
+        assert_eq!(vec![1, 2], left);
+        assert_eq!(vec![3], right);
+    }
+
+    #[test]
+    fn test_register_replace() {
+        let mut registry = StrategyRegistry::vec();
+        registry.register("append", ::merge2::vec::prepend);
+
+        let mut left = vec![1];
+        assert!(registry.merge("append", &mut left, &mut vec![2]));
+        assert_eq!(vec![2, 1], left);
+    }
+
+    #[test]
+    fn test_vec() {
+        let registry = StrategyRegistry::<Vec<u8>>::vec();
+        assert_eq!(
+            vec![
+                "append",
+                "clamp_len",
+                "interleave",
+                "merge",
+                "overwrite_if_empty",
+                "prepend",
+                "swap"
+            ],
+            registry.names().collect::<Vec<_>>()
+        );
+
+        let append = registry.get("append").unwrap();
+        let mut left = vec![1];
+        append(&mut left, &mut vec![2]);
+        assert_eq!(vec![1, 2], left);
+
+        let mut left = vec![1];
+        registry.merge("merge", &mut left, &mut vec![2]);
+        assert_eq!(vec![1], left);
+    }
+
+    #[test]
+    fn test_option() {
+        let registry = StrategyRegistry::<Option<u8>>::option();
+
+        let mut left = Some(1);
+        registry.merge("merge", &mut left, &mut Some(2));
+        assert_eq!(Some(1), left);
+        registry.merge("overwrite_some", &mut left, &mut Some(2));
+        assert_eq!(Some(2), left);
+        registry.merge("prefer_exclusive", &mut left, &mut Some(3));
+        assert_eq!(Some(2), left);
+    }
+}
+
+#[cfg(feature = "bytes")]
+mod bytes {
+    use super::test;
+    use crate::Merge;
+    use ::bytes::{Bytes, BytesMut};
+
+    #[test]
+    fn test_overwrite_empty() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(BytesMut, Bytes);
+
+        let s = |l: &'static [u8], r: &'static [u8]| S(BytesMut::from(l), Bytes::from_static(r));
+
+        test(s(b"", b""), s(b"", b""), s(b"", b""));
+        test(s(b"1", b"1"), s(b"", b""), s(b"1", b"1"));
+        test(s(b"0", b"0"), s(b"0", b"0"), s(b"", b""));
+        test(s(b"0", b"0"), s(b"0", b"0"), s(b"1", b"1"));
+    }
+
+    #[test]
+    fn test_append() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(#[merge(strategy = ::merge2::bytes::append)] BytesMut);
+
+        let s = |v: &'static [u8]| S(BytesMut::from(v));
+
+        test(s(b""), s(b""), s(b""));
+        test(s(b"1"), s(b""), s(b"1"));
+        test(s(b"0"), s(b"0"), s(b""));
+        test(s(b"01"), s(b"0"), s(b"1"));
+        test(s(b"01234"), s(b"012"), s(b"34"));
+        test(s(b"34012"), s(b"34"), s(b"012"));
+    }
+
+    #[test]
+    fn test_append_split() {
+        let mut left = BytesMut::from(&b"0123"[..]);
+        let mut right = left.split_off(2);
+        ::merge2::bytes::append(&mut left, &mut right);
+        assert_eq!(&b"0123"[..], &left[..]);
+        assert!(right.is_empty());
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod binary_heap {
+    use crate::Merge;
+    use std::collections::BinaryHeap;
+
+    fn test<S: Merge>(expected: &[u8], mut left: S, mut right: S, heap: fn(S) -> BinaryHeap<u8>) {
+        left.merge(&mut right);
+        let left = heap(left);
+        assert_eq!(expected.first(), left.peek());
+        let mut sorted = left.into_sorted_vec();
+        sorted.reverse();
+        assert_eq!(expected, sorted);
+    }
+
+    fn heap(v: &[u8]) -> BinaryHeap<u8> {
+        v.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_overwrite_empty() {
+        #[derive(Merge)]
+        struct S(BinaryHeap<u8>);
+
+        let s = |v: &[u8]| S(heap(v));
+
+        test(&[], s(&[]), s(&[]), |s| s.0);
+        test(&[1], s(&[]), s(&[1]), |s| s.0);
+        test(&[0], s(&[0]), s(&[]), |s| s.0);
+        test(&[0], s(&[0]), s(&[1]), |s| s.0);
+    }
+
+    #[test]
+    fn test_append() {
+        #[derive(Merge)]
+        struct S(#[merge(strategy = ::merge2::binary_heap::append)] BinaryHeap<u8>);
+
+        let s = |v: &[u8]| S(heap(v));
+
+        test(&[], s(&[]), s(&[]), |s| s.0);
+        test(&[1], s(&[]), s(&[1]), |s| s.0);
+        test(&[0], s(&[0]), s(&[]), |s| s.0);
+        test(&[1, 0], s(&[0]), s(&[1]), |s| s.0);
+        test(&[5, 4, 3, 2, 2, 1], s(&[2, 4, 1]), s(&[3, 2, 5]), |s| s.0);
+    }
+}
+
+#[cfg(feature = "std")]
+mod net {
+    use super::test;
+    use crate::Merge;
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+    const LOCALHOST: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+    const UNSPECIFIED: IpAddr = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+
+    #[test]
+    fn test_keep_left() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(IpAddr, SocketAddr);
+
+        let s = |ip, port| S(ip, SocketAddr::new(ip, port));
+
+        test(s(LOCALHOST, 80), s(LOCALHOST, 80), s(UNSPECIFIED, 8080));
+        test(s(UNSPECIFIED, 0), s(UNSPECIFIED, 0), s(LOCALHOST, 80));
+    }
+
+    #[test]
+    fn test_overwrite() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(
+            #[merge(strategy = ::merge2::any::overwrite_clone)] IpAddr,
+            #[merge(strategy = ::merge2::any::overwrite_clone)] SocketAddr,
+        );
+
+        let s = |ip, port| S(ip, SocketAddr::new(ip, port));
+
+        test(s(UNSPECIFIED, 8080), s(LOCALHOST, 80), s(UNSPECIFIED, 8080));
+        test(s(LOCALHOST, 80), s(UNSPECIFIED, 0), s(LOCALHOST, 80));
+        test(s(LOCALHOST, 80), s(LOCALHOST, 80), s(LOCALHOST, 80));
+    }
+}
+
+#[cfg(feature = "std")]
+mod path {
+    use super::test;
+    use crate::Merge;
+    use std::borrow::Cow;
+    use std::path::{Path, PathBuf};
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_cow_overwrite_empty() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S<'a>(Cow<'a, Path>);
+
+        let borrowed = |p: &'static str| S(Cow::Borrowed(Path::new(p)));
+
+        test(borrowed("/etc"), borrowed(""), borrowed("/etc"));
+        test(
+            S(Cow::Owned(PathBuf::from("/etc"))),
+            borrowed(""),
+            S(Cow::Owned(PathBuf::from("/etc"))),
+        );
+        test(borrowed("/usr"), borrowed("/usr"), borrowed("/etc"));
+        test(borrowed("/usr"), borrowed("/usr"), borrowed(""));
+    }
+
+    #[test]
+    fn test_shared_keep_left() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(Arc<Path>, Rc<Path>);
+
+        let s = |p: &str| S(Path::new(p).into(), Path::new(p).into());
+
+        test(s("/usr"), s("/usr"), s("/etc"));
+        test(s(""), s(""), s("/etc"));
+    }
+}
+
+#[cfg(feature = "std")]
+mod sync {
+    use crate::Merge;
+    use std::sync::{Arc, Mutex, PoisonError};
+
+    #[derive(Merge)]
+    struct S(#[merge(strategy = ::merge2::sync::mutex_merge)] Arc<Mutex<Option<u32>>>);
+
+    fn s(v: Option<u32>) -> S {
+        S(Arc::new(Mutex::new(v)))
+    }
+
+    fn test(expected: Option<u32>, mut left: S, mut right: S) {
+        left.merge(&mut right);
+        let value = left.0.lock().unwrap_or_else(PoisonError::into_inner);
+        assert_eq!(expected, *value);
+    }
+
+    #[test]
+    fn test_mutex_merge() {
+        test(Some(1), s(Some(1)), s(Some(2)));
+        test(Some(1), s(Some(1)), s(None));
+        test(Some(2), s(None), s(Some(2)));
+        test(None, s(None), s(None));
+    }
+
+    #[test]
+    fn test_mutex_merge_same() {
+        let left = s(None);
+        let right = S(left.0.clone());
+        test(None, left, right);
+    }
+
+    #[test]
+    fn test_mutex_merge_poisoned() {
+        let left = s(None);
+        let poison = left.0.clone();
+        let _ = std::thread::spawn(move || {
+            let _guard = poison.lock().unwrap();
+            panic!("poison the mutex");
+        })
+        .join();
+        assert!(left.0.is_poisoned());
+        test(Some(2), left, s(Some(2)));
+    }
+
+    #[test]
+    fn test_locks() {
+        use std::sync::RwLock;
+
+        #[derive(Merge)]
+        struct L(Mutex<Option<u32>>, RwLock<Option<u32>>);
+
+        let l = |a, b| L(Mutex::new(a), RwLock::new(b));
+        let test = |expected: (Option<u32>, Option<u32>), mut left: L, mut right: L| {
+            left.merge(&mut right);
+            let value = (left.0.into_inner().unwrap(), left.1.into_inner().unwrap());
+            assert_eq!(expected, value);
+        };
+
+        test((Some(1), Some(3)), l(Some(1), None), l(Some(2), Some(3)));
+        test((Some(2), Some(3)), l(None, Some(3)), l(Some(2), Some(4)));
+        test((None, None), l(None, None), l(None, None));
+    }
+
+    #[test]
+    fn test_mutex_poisoned() {
+        let left = Arc::new(Mutex::new(None));
+        let poison = left.clone();
+        let _ = std::thread::spawn(move || {
+            let _guard = poison.lock().unwrap();
+            panic!("poison the mutex");
+        })
+        .join();
+
+        let mut left = Arc::into_inner(left).unwrap();
+        left.merge(&mut Mutex::new(Some(2)));
+        assert_eq!(
+            Some(2),
+            left.into_inner().unwrap_or_else(PoisonError::into_inner)
+        );
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod chrono {
+    use super::test;
+    use crate::Merge;
+    use ::chrono::{DateTime, FixedOffset, NaiveDate, TimeZone, Utc};
+
+    fn utc(secs: i64) -> DateTime<Utc> {
+        Utc.timestamp_opt(secs, 0).unwrap()
+    }
+
+    #[test]
+    fn test_keep_left() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(DateTime<Utc>);
+
+        test(S(utc(1)), S(utc(1)), S(utc(2)));
+        test(S(utc(2)), S(utc(2)), S(utc(1)));
+    }
+
+    #[test]
+    fn test_latest() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(#[merge(strategy = ::merge2::chrono::latest)] DateTime<Utc>);
+
+        test(S(utc(2)), S(utc(1)), S(utc(2)));
+        test(S(utc(2)), S(utc(2)), S(utc(1)));
+        test(S(utc(1)), S(utc(1)), S(utc(1)));
+    }
+
+    #[test]
+    fn test_earliest() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(#[merge(strategy = ::merge2::chrono::earliest)] DateTime<Utc>);
+
+        test(S(utc(1)), S(utc(1)), S(utc(2)));
+        test(S(utc(1)), S(utc(2)), S(utc(1)));
+    }
+
+    #[test]
+    fn test_latest_offsets() {
+        let east = FixedOffset::east_opt(3600).unwrap();
+        let west = FixedOffset::west_opt(3600).unwrap();
+        // 00:30 at UTC+1 is earlier than 23:00 of the previous day at UTC-1
+        let mut left = east.with_ymd_and_hms(2024, 1, 2, 0, 30, 0).unwrap();
+        let mut right = west.with_ymd_and_hms(2024, 1, 1, 23, 0, 0).unwrap();
+        let expected = right;
+
+        merge2::chrono::latest(&mut left, &mut right);
+        assert_eq!(expected, left);
+        assert_eq!(west, *left.offset());
+    }
+
+    #[test]
+    fn test_naive_date() {
+        #[derive(Debug, Merge, PartialEq)]
+        struct S(
+            #[merge(strategy = ::merge2::chrono::max)] NaiveDate,
+            #[merge(strategy = ::merge2::chrono::min)] NaiveDate,
+        );
+
+        let d = |day| NaiveDate::from_ymd_opt(2024, 1, day).unwrap();
+
+        test(S(d(2), d(1)), S(d(1), d(1)), S(d(2), d(2)));
+        test(S(d(2), d(1)), S(d(2), d(2)), S(d(1), d(1)));
+    }
 }
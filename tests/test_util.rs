@@ -0,0 +1,66 @@
+#![cfg(all(feature = "derive", feature = "test-util"))]
+
+use merge2::test_util::{assert_identity_right, assert_keep_left};
+use merge2::Merge;
+
+#[test]
+fn test_option() {
+    assert_keep_left(Some(1u8));
+    assert_keep_left(None::<u8>);
+    assert_identity_right(Some(1u8));
+    assert_identity_right(None::<u8>);
+}
+
+#[test]
+fn test_strategies() {
+    #[derive(Clone, Debug, Default, PartialEq, Merge)]
+    struct S {
+        #[merge(strategy = ::merge2::bool::overwrite_false)]
+        flag: bool,
+        #[merge(strategy = ::merge2::any::overwrite_default)]
+        value: u8,
+        #[merge(strategy = ::merge2::ord::max)]
+        max: u8,
+    }
+
+    let s = S {
+        flag: true,
+        value: 1,
+        max: 2,
+    };
+    assert_keep_left(s.clone());
+    assert_identity_right(s);
+    assert_identity_right(S::default());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_collections() {
+    #[derive(Clone, Debug, Default, PartialEq, Merge)]
+    struct S(
+        #[merge(strategy = ::merge2::vec::append)] Vec<u8>,
+        #[merge(strategy = ::merge2::string::append)] String,
+    );
+
+    assert_identity_right(S(vec![1, 2], "12".to_owned()));
+    assert_keep_left(S(vec![], String::new()));
+}
+
+#[test]
+#[should_panic(expected = "merging a value with itself changed it")]
+#[cfg(feature = "alloc")]
+fn test_keep_left_fails() {
+    #[derive(Clone, Debug, Default, PartialEq, Merge)]
+    struct S(#[merge(strategy = ::merge2::vec::append)] Vec<u8>);
+
+    assert_keep_left(S(vec![1]));
+}
+
+#[test]
+#[should_panic(expected = "merging a default value changed the value")]
+fn test_identity_right_fails() {
+    #[derive(Clone, Debug, Default, PartialEq, Merge)]
+    struct S(#[merge(strategy = ::merge2::any::overwrite)] u8);
+
+    assert_identity_right(S(1));
+}